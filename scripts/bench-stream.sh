@@ -0,0 +1,42 @@
+#!/bin/sh
+# Compares the time and peak memory of reading a large JSON array with and without
+# `--stream`. Linux only, since the peak memory is read from /proc.
+#
+#     scripts/bench-stream.sh [objects]    # default: 1000000
+set -eu
+
+objects=${1:-1000000}
+binary=target/release/json-to-struct
+input=$(mktemp --suffix .json)
+trap 'rm -f "$input"' EXIT
+
+cargo build --release --quiet
+awk -v n="$objects" 'BEGIN {
+    printf "["
+    for (i = 0; i < n; i++) {
+        if (i > 0) printf ","
+        printf "{\"id\":%d,\"name\":\"user%d\",\"active\":%s,", i, i % 1000, i % 2 ? "true" : "false"
+        printf "\"address\":{\"city\":\"city%d\",\"zip\":null},\"tags\":[\"a\",\"b\"]}", i % 50
+    }
+    printf "]"
+}' > "$input"
+echo "input: $objects objects, $(du -h "$input" | cut -f1)"
+
+run() {
+    start=$(date +%s.%N)
+    "$binary" "$input" rust "$@" > /dev/null &
+    pid=$!
+    peak=0
+    while kill -0 "$pid" 2> /dev/null; do
+        rss=$(awk '/VmHWM/ { print $2 }' "/proc/$pid/status" 2> /dev/null || true)
+        peak=${rss:-$peak}
+        sleep 0.05
+    done
+    wait "$pid"
+    end=$(date +%s.%N)
+    seconds=$(awk -v start="$start" -v end="$end" 'BEGIN { print end - start }')
+    printf '%-10s %6.2fs %8d KiB peak\n' "${1:-default}" "$seconds" "$peak"
+}
+
+run
+run --stream
@@ -0,0 +1,210 @@
+//! Finding the fields whose Rust type is a judgement call, for `--interactive`.
+
+use crate::fields::{array_elements, merge_fields};
+use crate::jsonschema::json_schema_type;
+use crate::rust::{example_value, Resolution};
+use crate::Options;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Most distinct strings a field can take to be offered as an enum.
+const MAX_ENUM_CANDIDATE_VARIANTS: usize = 10;
+
+/// Fewest keys an object needs to be offered as a `HashMap`.
+const MIN_MAP_CANDIDATE_KEYS: usize = 3;
+
+/// Most examples shown for an ambiguous field.
+const MAX_EXAMPLES: usize = 5;
+
+/// A field whose values allow more than one reasonable Rust type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ambiguity {
+    /// The JSON key, which resolutions are matched by.
+    pub key: String,
+    /// What makes the type ambiguous, e.g. `values of types number, string`.
+    pub reason: String,
+    /// A few distinct observed values, as JSON.
+    pub examples: Vec<String>,
+    /// The candidate types, starting with the one used without a resolution.
+    pub choices: Vec<Resolution>,
+}
+
+/// Lists the fields of the objects in `values`, nested ones included, whose type the
+/// Rust generator would have to guess: values of conflicting types, strings with few
+/// distinct values and objects that could be maps. Each key is listed once, where it
+/// first appears.
+pub fn find_ambiguities(values: &[Value], options: &Options) -> Vec<Ambiguity> {
+    let values: Vec<&Value> = values.iter().collect();
+    let mut ambiguities = Vec::new();
+    collect_ambiguities(&values, options, &mut ambiguities);
+    ambiguities
+}
+
+fn collect_ambiguities(values: &[&Value], options: &Options, ambiguities: &mut Vec<Ambiguity>) {
+    for (key, field) in merge_fields(values, options) {
+        let present: Vec<&Value> = field
+            .values
+            .iter()
+            .filter(|value| !value.is_null())
+            .copied()
+            .collect();
+        if ambiguities.iter().all(|ambiguity| ambiguity.key != key) {
+            if let Some((reason, choices)) = ambiguity(&present, options) {
+                ambiguities.push(Ambiguity {
+                    key: key.clone(),
+                    reason,
+                    examples: examples(&present),
+                    choices,
+                });
+            }
+        }
+
+        let nested: Vec<&Value> = if present.iter().all(|value| value.is_array()) {
+            array_elements(&present)
+        } else {
+            present
+        };
+        let objects: Vec<&Value> = nested
+            .into_iter()
+            .filter(|value| value.is_object())
+            .collect();
+        if !objects.is_empty() {
+            collect_ambiguities(&objects, options, ambiguities);
+        }
+    }
+}
+
+/// Explains why the type of `present` is ambiguous and lists the candidate types,
+/// the current default first, or returns `None` when there is only one sensible type.
+fn ambiguity(present: &[&Value], options: &Options) -> Option<(String, Vec<Resolution>)> {
+    let kinds: BTreeSet<&str> = present
+        .iter()
+        .map(|value| json_schema_type(value))
+        .collect();
+    let kinds: Vec<&str> = kinds
+        .into_iter()
+        .map(|kind| if kind == "integer" { "number" } else { kind })
+        .collect::<BTreeSet<&str>>()
+        .into_iter()
+        .collect();
+    match kinds.as_slice() {
+        [] => None,
+        ["object"] => {
+            let maps: Vec<_> = present
+                .iter()
+                .filter_map(|value| value.as_object())
+                .collect();
+            let keys: BTreeSet<&String> = maps.iter().flat_map(|map| map.keys()).collect();
+            let kinds: BTreeSet<&str> = maps
+                .iter()
+                .flat_map(|map| map.values())
+                .filter(|value| !value.is_null())
+                .map(|value| json_schema_type(value))
+                .collect();
+            if keys.len() < MIN_MAP_CANDIDATE_KEYS || kinds.len() != 1 {
+                return None;
+            }
+            let mut choices = vec![Resolution::Struct, Resolution::HashMap];
+            if options
+                .rust
+                .map_threshold
+                .is_some_and(|threshold| keys.len() >= threshold)
+            {
+                choices.reverse();
+            }
+            Some((
+                format!("{} keys, all holding one type of value", keys.len()),
+                choices,
+            ))
+        }
+        ["string"] => {
+            let distinct: BTreeSet<&str> =
+                present.iter().filter_map(|value| value.as_str()).collect();
+            if distinct.len() < 2
+                || distinct.len() > MAX_ENUM_CANDIDATE_VARIANTS
+                || distinct.len() == present.len()
+            {
+                return None;
+            }
+            let mut choices = vec![Resolution::String, Resolution::Enum];
+            if options
+                .rust
+                .infer_enums
+                .is_some_and(|max| distinct.len() <= max)
+            {
+                choices.reverse();
+            }
+            Some((format!("{} distinct strings", distinct.len()), choices))
+        }
+        [_] => None,
+        _ => {
+            let mut choices = vec![Resolution::Value, Resolution::UntaggedEnum];
+            if options.rust.conflict_enums {
+                choices.reverse();
+            }
+            Some((format!("values of types {}", kinds.join(", ")), choices))
+        }
+    }
+}
+
+/// Renders the first few distinct values as JSON.
+fn examples(present: &[&Value]) -> Vec<String> {
+    let mut examples: Vec<String> = Vec::new();
+    for value in present {
+        let example = example_value(&[value]).expect("values are not null");
+        if !examples.contains(&example) {
+            examples.push(example);
+            if examples.len() == MAX_EXAMPLES {
+                break;
+            }
+        }
+    }
+    examples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_find_ambiguities() {
+        let json_value = json!([
+            { "id": 1, "status": "open", "code": 7, "scores": { "a": 1, "b": 2, "c": 3 } },
+            { "id": 2, "status": "closed", "code": "x", "items": [{ "status": "open", "kind": 1 }] },
+            { "id": 3, "status": "open", "items": [{ "kind": "big" }] }
+        ]);
+
+        let ambiguities = find_ambiguities(json_value.as_array().unwrap(), &Options::default());
+        let summary: Vec<(&str, &str, Vec<Resolution>)> = ambiguities
+            .iter()
+            .map(|a| (a.key.as_str(), a.reason.as_str(), a.choices.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    "code",
+                    "values of types number, string",
+                    vec![Resolution::Value, Resolution::UntaggedEnum]
+                ),
+                (
+                    "kind",
+                    "values of types number, string",
+                    vec![Resolution::Value, Resolution::UntaggedEnum]
+                ),
+                (
+                    "scores",
+                    "3 keys, all holding one type of value",
+                    vec![Resolution::Struct, Resolution::HashMap]
+                ),
+                (
+                    "status",
+                    "2 distinct strings",
+                    vec![Resolution::String, Resolution::Enum]
+                ),
+            ]
+        );
+        assert_eq!(ambiguities[0].examples, ["7", "\"x\""]);
+    }
+}
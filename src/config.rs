@@ -0,0 +1,105 @@
+//! Defaults read from a `json-to-struct.toml` file.
+
+use serde::Deserialize;
+
+/// Default flags read from a config file. Every key is optional and named after the
+/// command-line flag it sets; flags given on the command line take precedence.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub(crate) struct Config {
+    pub(crate) format: Option<String>,
+    pub(crate) name: Option<String>,
+    pub(crate) indent: Option<Indent>,
+    pub(crate) preserve_order: bool,
+    pub(crate) ts_null_style: Option<String>,
+    pub(crate) derive: Option<String>,
+    #[serde(rename = "pub")]
+    pub(crate) public: bool,
+    pub(crate) conflict_enums: bool,
+    pub(crate) chrono: bool,
+    pub(crate) uuid: bool,
+    pub(crate) serde_skip_none: bool,
+    pub(crate) narrow_ints: bool,
+    pub(crate) type_override: Option<String>,
+    pub(crate) name_prefix: Option<String>,
+    pub(crate) name_suffix: Option<String>,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) no_singularize: bool,
+    pub(crate) all_required: bool,
+    pub(crate) all_optional: bool,
+    pub(crate) compact: bool,
+    pub(crate) compact_max_fields: Option<usize>,
+    pub(crate) null_policy: Option<String>,
+    pub(crate) smart_strings: bool,
+    pub(crate) split_shapes: bool,
+    pub(crate) tag_style: Option<String>,
+    pub(crate) tag_key: Option<String>,
+    pub(crate) non_exhaustive: bool,
+    pub(crate) module: Option<String>,
+    pub(crate) tryfrom: bool,
+    pub(crate) emit_tests: bool,
+    pub(crate) with_imports: bool,
+    pub(crate) default: bool,
+    pub(crate) decode_base64: bool,
+    pub(crate) coerce_numeric_strings: bool,
+    pub(crate) newtypes: bool,
+    pub(crate) newtype_suffixes: Option<String>,
+    pub(crate) flat: bool,
+    pub(crate) shared: Option<String>,
+    pub(crate) prefer_f32: bool,
+    pub(crate) with_examples: bool,
+    pub(crate) decimal: bool,
+    pub(crate) with_extra: bool,
+    pub(crate) deny_unknown: bool,
+    pub(crate) keep_original_names: bool,
+    pub(crate) rename_all: bool,
+    pub(crate) map_threshold: Option<usize>,
+    pub(crate) builder: bool,
+    pub(crate) accessors: bool,
+    pub(crate) infer_enums: bool,
+    pub(crate) max_enum_variants: Option<usize>,
+}
+
+/// An `indent` value, written either as a number of spaces or as a string like the
+/// `--indent` flag takes.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Indent {
+    Spaces(usize),
+    Text(String),
+}
+
+impl Indent {
+    pub(crate) fn as_arg(&self) -> String {
+        match self {
+            Indent::Spaces(spaces) => spaces.to_string(),
+            Indent::Text(text) => text.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config: Config =
+            toml::from_str("format = \"typescript\"\nindent = 2\npub = true\nderive = \"serde\"\n")
+                .unwrap();
+        assert_eq!(config.format.as_deref(), Some("typescript"));
+        assert_eq!(config.indent.unwrap().as_arg(), "2");
+        assert!(config.public);
+        assert!(!config.chrono);
+
+        let config: Config = toml::from_str(
+            "keep-original-names = true\nrename-all = true\nname-suffix = \"Dto\"\nmax-enum-variants = 3\n",
+        )
+        .unwrap();
+        assert!(config.keep_original_names && config.rename_all);
+        assert_eq!(config.name_suffix.as_deref(), Some("Dto"));
+        assert_eq!(config.max_enum_variants, Some(3));
+
+        assert!(toml::from_str::<Config>("colour = true").is_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! C# class generation.
+
+use crate::fields::{merge_fields, value_field_type};
+use crate::naming::{dedupe_field_names, upper_camel_case};
+use crate::Options;
+use serde_json::Value;
+
+/// C# value types, which need a `?` to hold `null`.
+const CSHARP_VALUE_TYPES: [&str; 3] = ["bool", "double", "long"];
+
+/// Prints a class with an auto-property for every key. Each property carries a
+/// `[JsonPropertyName]` attribute with the original key, and optional value-type
+/// properties are nullable.
+pub fn print_csharp_class(values: &[Value], options: &Options) -> String {
+    let values: Vec<&Value> = values.iter().collect();
+    let indent = options.indent.as_deref().unwrap_or("    ");
+    let name = options.type_name(&options.name);
+    let fields = merge_fields(&values, options);
+    let keys: Vec<&str> = fields.iter().map(|(key, _)| key.as_str()).collect();
+    let mut names: Vec<String> = keys
+        .iter()
+        .map(|key| {
+            let property = upper_camel_case(key);
+            // Members can't share the name of their class (CS0542).
+            if property == name {
+                format!("{}Value", property)
+            } else {
+                property
+            }
+        })
+        .collect();
+    dedupe_field_names(&keys, &mut names);
+    let mut output = String::new();
+
+    for ((key, field), property) in fields.iter().zip(names) {
+        let mut ty = csharp_field_type(&field.values);
+        if field.is_optional && CSHARP_VALUE_TYPES.contains(&ty.as_str()) {
+            ty.push('?');
+        }
+        output.push_str(&format!(
+            "{}[JsonPropertyName({})]\n",
+            indent,
+            Value::String(key.clone())
+        ));
+        output.push_str(&format!(
+            "{}public {} {} {{ get; set; }}\n",
+            indent, ty, property
+        ));
+    }
+
+    format!("public class {}\n{{\n{}}}", name, output)
+}
+
+fn csharp_field_type(values: &[&Value]) -> String {
+    value_field_type(values, csharp_value_type, "object", |ty| {
+        format!("List<{}>", ty)
+    })
+}
+
+/// Maps a single JSON value to its C# type.
+pub fn csharp_value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "object",
+        Value::Bool(_) => "bool",
+        Value::Number(n) if n.is_f64() => "double",
+        Value::Number(_) => "long",
+        Value::String(_) => "string",
+        Value::Array(_) => "List<object>",
+        Value::Object(_) => "Dictionary<string, object>",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_output;
+    use serde_json::json;
+
+    #[test]
+    fn test_print_csharp_class() {
+        let json_value = json!([
+            { "first_name": "Alice", "age": 30, "scores": [1.5, 2], "meta": {} },
+            { "first_name": "Bob", "scores": [], "meta": {}, "nickname": null }
+        ]);
+
+        let expected_output = "public class Data\n{\n[JsonPropertyName(\"age\")]\npublic long? Age { get; set; }\n[JsonPropertyName(\"first_name\")]\npublic string FirstName { get; set; }\n[JsonPropertyName(\"meta\")]\npublic Dictionary<string, object> Meta { get; set; }\n[JsonPropertyName(\"nickname\")]\npublic object Nickname { get; set; }\n[JsonPropertyName(\"scores\")]\npublic List<double> Scores { get; set; }\n}";
+
+        let actual_output = format_output(&print_csharp_class(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_csharp_class_property_names() {
+        let json_value = json!([{ "userId": 1, "user_id": 2, "data": 3, "q\"k": "a" }]);
+
+        let expected_output = "public class Data\n{\n[JsonPropertyName(\"data\")]\npublic long DataValue { get; set; }\n[JsonPropertyName(\"q\\\"k\")]\npublic string QK { get; set; }\n[JsonPropertyName(\"userId\")]\npublic long UserId { get; set; }\n[JsonPropertyName(\"user_id\")]\npublic long UserId2 { get; set; }\n}";
+
+        let actual_output = format_output(&print_csharp_class(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+}
@@ -0,0 +1,247 @@
+//! Dart class generation, with the `fromJson`/`toJson` pair Flutter code expects.
+
+use crate::fields::{merge_fields, value_field_type};
+use crate::naming::{camel_case, dedupe_field_names};
+use crate::Options;
+use serde_json::Value;
+
+/// Reserved words that can't be used as field names; a trailing `_` is added to them.
+const DART_KEYWORDS: [&str; 33] = [
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else",
+    "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null",
+    "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var", "void", "while",
+    "with",
+];
+
+/// Prints a class with a `final` field for every key, a constructor with named
+/// parameters, a `fromJson` factory and a `toJson` method. Optional fields are
+/// nullable and the only parameters that aren't `required`.
+pub fn print_dart_class(values: &[Value], options: &Options) -> String {
+    let values: Vec<&Value> = values.iter().collect();
+    let indent = options.indent.as_deref().unwrap_or("  ");
+    let name = options.type_name(&options.name);
+    let mut fields = String::new();
+    let mut params = String::new();
+    let mut from_json = String::new();
+    let mut to_json = String::new();
+
+    let merged = merge_fields(&values, options);
+    let keys: Vec<&str> = merged.iter().map(|(key, _)| key.as_str()).collect();
+    let mut names: Vec<String> = keys.iter().map(|key| camel_case(key)).collect();
+    dedupe_field_names(&keys, &mut names);
+
+    for ((key, field), mut field_name) in merged.iter().zip(names) {
+        let ty = dart_field_type(&field.values);
+        if DART_KEYWORDS.contains(&field_name.as_str()) {
+            field_name.push('_');
+        }
+        let nullable = field.is_optional && ty != "dynamic";
+        let key = dart_string(key);
+        fields.push_str(&format!(
+            "{}final {}{} {};\n",
+            indent,
+            ty,
+            if nullable { "?" } else { "" },
+            field_name
+        ));
+        params.push_str(&format!(
+            "{}{}{}this.{},\n",
+            indent,
+            indent,
+            if field.is_optional { "" } else { "required " },
+            field_name
+        ));
+        from_json.push_str(&format!(
+            "{}{}{}: {},\n",
+            indent,
+            indent,
+            field_name,
+            from_json_expr(&ty, &format!("json[{}]", key), nullable)
+        ));
+        to_json.push_str(&format!("{}{}{}: {},\n", indent, indent, key, field_name));
+    }
+
+    let mut output = format!("class {} {{\n{}", name, fields);
+    if !fields.is_empty() {
+        output.push('\n');
+    }
+    if params.is_empty() {
+        output.push_str(&format!("{}{}();\n\n", indent, name));
+    } else {
+        output.push_str(&format!(
+            "{}{}({{\n{}{}}});\n\n",
+            indent, name, params, indent
+        ));
+    }
+    output.push_str(&format!(
+        "{}factory {}.fromJson(Map<String, dynamic> json) => {}(\n{}{});\n\n",
+        indent, name, name, from_json, indent
+    ));
+    output.push_str(&format!(
+        "{}Map<String, dynamic> toJson() => {{\n{}{}}};\n}}",
+        indent, to_json, indent
+    ));
+    output
+}
+
+/// Converts the JSON value `expr` to `ty`. Numbers are read as `num` so integers in a
+/// `double` field don't fail the cast, and list elements are cast one by one.
+fn from_json_expr(ty: &str, expr: &str, nullable: bool) -> String {
+    let q = if nullable { "?" } else { "" };
+    match ty {
+        "dynamic" => expr.to_string(),
+        "double" => format!("({} as num{}){}.toDouble()", expr, q, q),
+        "List<double>" => format!(
+            "({} as List<dynamic>{}){}.map((e) => (e as num).toDouble()).toList()",
+            expr, q, q
+        ),
+        "List<dynamic>" => format!("{} as List<dynamic>{}", expr, q),
+        _ if ty.starts_with("List<") => format!(
+            "({} as List<dynamic>{}){}.cast<{}>()",
+            expr,
+            q,
+            q,
+            &ty["List<".len()..ty.len() - 1]
+        ),
+        _ => format!("{} as {}{}", expr, ty, q),
+    }
+}
+
+/// Quotes a JSON key as a Dart string literal.
+fn dart_string(key: &str) -> String {
+    let mut literal = String::from('\'');
+    for c in key.chars() {
+        match c {
+            '\\' | '\'' | '$' => {
+                literal.push('\\');
+                literal.push(c);
+            }
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c.is_control() => literal.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('\'');
+    literal
+}
+
+/// Lists are typed by their elements alone, so a list of lists is `List<dynamic>`.
+fn dart_field_type(values: &[&Value]) -> String {
+    let value_type = |value: &Value| match value {
+        Value::Array(_) => "dynamic",
+        value => dart_value_type(value),
+    };
+    value_field_type(values, value_type, "dynamic", |ty| format!("List<{}>", ty))
+}
+
+/// Maps a single JSON value to its Dart type.
+pub fn dart_value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "dynamic",
+        Value::Bool(_) => "bool",
+        Value::Number(n) if n.is_f64() => "double",
+        Value::Number(_) => "int",
+        Value::String(_) => "String",
+        Value::Array(_) => "List<dynamic>",
+        Value::Object(_) => "Map<String, dynamic>",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_print_dart_class() {
+        let json_value = json!([
+            { "first_name": "Alice", "age": 30, "scores": [1.5, 2], "tags": ["a"], "class": true },
+            { "first_name": "Bob", "age": 25, "scores": [], "tags": [], "address": {} }
+        ]);
+
+        let expected_output = "class Data {
+  final Map<String, dynamic>? address;
+  final int age;
+  final bool? class_;
+  final String firstName;
+  final List<double> scores;
+  final List<String> tags;
+
+  Data({
+    this.address,
+    required this.age,
+    this.class_,
+    required this.firstName,
+    required this.scores,
+    required this.tags,
+  });
+
+  factory Data.fromJson(Map<String, dynamic> json) => Data(
+    address: json['address'] as Map<String, dynamic>?,
+    age: json['age'] as int,
+    class_: json['class'] as bool?,
+    firstName: json['first_name'] as String,
+    scores: (json['scores'] as List<dynamic>).map((e) => (e as num).toDouble()).toList(),
+    tags: (json['tags'] as List<dynamic>).cast<String>(),
+  );
+
+  Map<String, dynamic> toJson() => {
+    'address': address,
+    'age': age,
+    'class': class_,
+    'first_name': firstName,
+    'scores': scores,
+    'tags': tags,
+  };
+}";
+
+        assert_eq!(
+            print_dart_class(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_dart_class_renamed_fields() {
+        let json_value = json!([{ "$type": "a", "a\\b": 1, "it's": 2, "userId": 3, "user_id": 4 }]);
+
+        let expected_output = r"class Data {
+  final String type;
+  final int aB;
+  final int itS;
+  final int userId;
+  final int userId2;
+
+  Data({
+    required this.type,
+    required this.aB,
+    required this.itS,
+    required this.userId,
+    required this.userId2,
+  });
+
+  factory Data.fromJson(Map<String, dynamic> json) => Data(
+    type: json['\$type'] as String,
+    aB: json['a\\b'] as int,
+    itS: json['it\'s'] as int,
+    userId: json['userId'] as int,
+    userId2: json['user_id'] as int,
+  );
+
+  Map<String, dynamic> toJson() => {
+    '\$type': type,
+    'a\\b': aB,
+    'it\'s': itS,
+    'userId': userId,
+    'user_id': userId2,
+  };
+}";
+
+        assert_eq!(
+            print_dart_class(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! Comparing the inferred schemas of two samples, for tracking how an API changes.
+
+use crate::naming::{pascal_case, singularize};
+use crate::schema::{InferredField, InferredType, ScalarType};
+
+/// Lists how `new` differs from `old`, one line per change, with types written the
+/// way the Rust generator would: `+ email: String` for an added field, `- name: String`
+/// for a removed one and `~ age: i64 -> f64` for a type change. Fields of nested
+/// objects are named by their path, such as `address.city` or `pets[].name`.
+pub fn diff_schemas(old: &InferredType, new: &InferredType) -> Vec<String> {
+    let mut changes = Vec::new();
+    diff_types("", old, new, &mut changes);
+    changes
+}
+
+fn diff_types(path: &str, old: &InferredType, new: &InferredType, changes: &mut Vec<String>) {
+    match (old, new) {
+        _ if old == new => {}
+        (InferredType::Object(old), InferredType::Object(new)) => {
+            diff_fields(path, old, new, changes)
+        }
+        (InferredType::Array(old), InferredType::Array(new)) => {
+            diff_types(&format!("{}[]", path), old, new, changes)
+        }
+        (InferredType::Optional(old), InferredType::Optional(new)) => {
+            diff_types(path, old, new, changes)
+        }
+        _ => {
+            let field = path.trim_end_matches("[]");
+            let key = field.rsplit('.').next().unwrap_or(field);
+            let key = if field.len() < path.len() {
+                singularize(key)
+            } else {
+                key.to_string()
+            };
+            changes.push(format!(
+                "~ {}: {} -> {}",
+                path,
+                rust_type_name(&key, old),
+                rust_type_name(&key, new)
+            ));
+            // A field that became optional, or a list, can still have changed inside.
+            if let (Some((_, old)), Some((suffix, new))) = (objects(old), objects(new)) {
+                diff_fields(&format!("{}{}", path, suffix), old, new, changes);
+            }
+        }
+    }
+}
+
+fn diff_fields(
+    path: &str,
+    old: &[InferredField],
+    new: &[InferredField],
+    changes: &mut Vec<String>,
+) {
+    let field_path = |name: &str| {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", path, name)
+        }
+    };
+    for field in old {
+        if !new.iter().any(|other| other.name == field.name) {
+            changes.push(format!(
+                "- {}: {}",
+                field_path(&field.name),
+                rust_type_name(&field.name, &field_type(field))
+            ));
+        }
+    }
+    for field in new {
+        match old.iter().find(|other| other.name == field.name) {
+            Some(old_field) => diff_types(
+                &field_path(&field.name),
+                &field_type(old_field),
+                &field_type(field),
+                changes,
+            ),
+            None => changes.push(format!(
+                "+ {}: {}",
+                field_path(&field.name),
+                rust_type_name(&field.name, &field_type(field))
+            )),
+        }
+    }
+}
+
+/// The type of a field, made optional when the key is missing from some objects.
+fn field_type(field: &InferredField) -> InferredType {
+    match &field.ty {
+        InferredType::Optional(_) => field.ty.clone(),
+        ty if !field.required => InferredType::Optional(Box::new(ty.clone())),
+        ty => ty.clone(),
+    }
+}
+
+/// Finds the object inside any `Option` and `Vec` wrappers, returning the fields along
+/// with the path suffix, such as `[]`, that leads to them.
+fn objects(ty: &InferredType) -> Option<(String, &[InferredField])> {
+    match ty {
+        InferredType::Object(fields) => Some((String::new(), fields)),
+        InferredType::Optional(inner) => objects(inner),
+        InferredType::Array(items) => {
+            objects(items).map(|(suffix, fields)| (format!("[]{}", suffix), fields))
+        }
+        _ => None,
+    }
+}
+
+/// Writes an inferred type as the Rust type generated for the field `key`.
+fn rust_type_name(key: &str, ty: &InferredType) -> String {
+    match ty {
+        InferredType::Unknown | InferredType::Union(_) => "Value".to_string(),
+        InferredType::Scalar(ScalarType::Bool) => "bool".to_string(),
+        InferredType::Scalar(ScalarType::Integer) => "i64".to_string(),
+        InferredType::Scalar(ScalarType::Float) => "f64".to_string(),
+        InferredType::Scalar(ScalarType::String) => "String".to_string(),
+        InferredType::Object(_) => pascal_case(key),
+        InferredType::Array(items) => format!("Vec<{}>", rust_type_name(&singularize(key), items)),
+        InferredType::Optional(inner) => format!("Option<{}>", rust_type_name(key, inner)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infer_schema;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_schemas() {
+        let old = json!([
+            { "name": "alice", "age": 30, "address": { "city": "Paris" }, "pets": [{ "kind": "cat" }] }
+        ]);
+        let new = json!([
+            { "age": 30.5, "email": "a@b.c", "address": { "city": "Paris", "zip": "75001" }, "pets": [{ "kind": 1 }] },
+            { "age": 41, "pets": [] }
+        ]);
+
+        let changes = diff_schemas(
+            &infer_schema(old.as_array().unwrap()),
+            &infer_schema(new.as_array().unwrap()),
+        );
+        assert_eq!(
+            changes,
+            [
+                "- name: String",
+                "~ address: Address -> Option<Address>",
+                "+ address.zip: String",
+                "~ age: i64 -> f64",
+                "+ email: Option<String>",
+                "~ pets[].kind: String -> i64",
+            ]
+        );
+        assert!(diff_schemas(
+            &infer_schema(&[json!({ "a": 1 })]),
+            &infer_schema(&[json!({ "a": 2 })])
+        )
+        .is_empty());
+    }
+}
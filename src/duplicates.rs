@@ -0,0 +1,135 @@
+//! Finding keys repeated within one JSON object, which `serde_json` silently collapses
+//! to their last value.
+
+use serde::de::{DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Where a value sits in the document, kept as borrowed segments so a path is only
+/// written out when a duplicate is found.
+enum Path<'a> {
+    Root,
+    Key(&'a Path<'a>, &'a str),
+    Index(&'a Path<'a>, usize),
+}
+
+impl fmt::Display for Path<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Path::Root => Ok(()),
+            Path::Key(Path::Root, key) => write!(f, "{}", key),
+            Path::Key(parent, key) => write!(f, "{}.{}", parent, key),
+            Path::Index(parent, index) => write!(f, "{}[{}]", parent, index),
+        }
+    }
+}
+
+/// Walks a JSON value without building it, recording the path of every key that
+/// appears a second time in the same object.
+struct KeyChecker<'a> {
+    path: &'a Path<'a>,
+    duplicates: &'a mut Vec<String>,
+}
+
+impl<'de> DeserializeSeed<'de> for KeyChecker<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for KeyChecker<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "any JSON value")
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_str<E>(self, _: &str) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_unit<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut index = 0;
+        while seq
+            .next_element_seed(KeyChecker {
+                path: &Path::Index(self.path, index),
+                duplicates: self.duplicates,
+            })?
+            .is_some()
+        {
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let mut seen = HashSet::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let path = Path::Key(self.path, &key);
+            if seen.contains(&key) {
+                self.duplicates.push(path.to_string());
+            }
+            map.next_value_seed(KeyChecker {
+                path: &path,
+                duplicates: self.duplicates,
+            })?;
+            seen.insert(key);
+        }
+        Ok(())
+    }
+}
+
+/// Lists the paths, such as `users[3].email`, of the keys repeated within an object
+/// in the JSON document `content`. Invalid JSON gives no duplicates, leaving the error
+/// to the real parse.
+pub(crate) fn duplicate_keys(content: &str) -> Vec<String> {
+    let mut duplicates = Vec::new();
+    let mut deserializer = serde_json::Deserializer::from_str(content);
+    let checker = KeyChecker {
+        path: &Path::Root,
+        duplicates: &mut duplicates,
+    };
+    match checker.deserialize(&mut deserializer) {
+        Ok(()) => duplicates,
+        Err(_) => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_keys() {
+        assert_eq!(
+            duplicate_keys(
+                r#"[{"id": 1, "id": 2}, {"user": {"email": "a", "name": "b", "email": "c"}}]"#
+            ),
+            ["[0].id", "[1].user.email"]
+        );
+        assert!(duplicate_keys(r#"{"a": [{"b": 1}, {"b": 2}], "c": {"b": 3}}"#).is_empty());
+        assert!(duplicate_keys(r#"{"a": 1, "a""#).is_empty());
+    }
+}
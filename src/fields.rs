@@ -0,0 +1,161 @@
+//! Merging of the keys observed across a list of sample objects.
+
+use crate::Options;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+/// Merged information about a single key across every object it appears in.
+pub(crate) struct Field<'a> {
+    /// Set when the key is missing from or `null` in at least one object, i.e.
+    /// `is_missing || is_nullable`. Rust's `--null-policy` picks from the two instead.
+    pub(crate) is_optional: bool,
+    /// Set when the key is absent from at least one object.
+    pub(crate) is_missing: bool,
+    /// Set when the key is present but `null` in at least one object.
+    pub(crate) is_nullable: bool,
+    /// Set when the key was present but `null` in every object it appeared in, so
+    /// its real type is unknown.
+    pub(crate) is_always_null: bool,
+    /// Index, among the merged objects, of the first one the key is absent from.
+    pub(crate) first_missing: Option<usize>,
+    /// Index, among the merged objects, of the first one the key is `null` in.
+    pub(crate) first_null: Option<usize>,
+    pub(crate) values: Vec<&'a Value>,
+}
+
+impl Field<'_> {
+    /// Names the kinds of JSON value observed for the field, such as `String` or
+    /// `Number`, in order and without `null`.
+    pub(crate) fn observed_kinds(&self) -> Vec<&'static str> {
+        let mut kinds: Vec<&'static str> = self
+            .values
+            .iter()
+            .filter_map(|value| match value {
+                Value::Null => None,
+                Value::Bool(_) => Some("Bool"),
+                Value::Number(_) => Some("Number"),
+                Value::String(_) => Some("String"),
+                Value::Array(_) => Some("Array"),
+                Value::Object(_) => Some("Object"),
+            })
+            .collect();
+        kinds.sort_unstable();
+        kinds.dedup();
+        kinds
+    }
+}
+
+/// Merges the keys of every object in `values`. A field is optional when it is missing
+/// from at least one object or is `null` in any of them, unless `options.all_required`
+/// is set. With `options.all_optional`, every field counts as missing from some object.
+///
+/// Fields are sorted by key unless `options.preserve_order` is set, in which case they
+/// keep the order in which each key first appeared.
+pub(crate) fn merge_fields<'a>(
+    values: &[&'a Value],
+    options: &Options,
+) -> Vec<(String, Field<'a>)> {
+    let mut fields: Vec<(String, Field)> = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    let mut objects = 0;
+
+    for value in values {
+        if let Value::Object(map) = value {
+            let index = objects;
+            objects += 1;
+            for (key, value) in map {
+                let position = *positions.entry(key).or_insert_with(|| {
+                    fields.push((
+                        key.clone(),
+                        Field {
+                            is_optional: false,
+                            is_missing: false,
+                            is_nullable: false,
+                            is_always_null: false,
+                            first_missing: None,
+                            first_null: None,
+                            values: Vec::new(),
+                        },
+                    ));
+                    fields.len() - 1
+                });
+                let field = &mut fields[position].1;
+                // Until the key is first missing, it has been in every object so far.
+                if field.first_missing.is_none() && field.values.len() < index {
+                    field.first_missing = Some(field.values.len());
+                }
+                if value.is_null() && field.first_null.is_none() {
+                    field.first_null = Some(index);
+                }
+                field.values.push(value);
+            }
+        }
+    }
+
+    for (_, field) in fields.iter_mut() {
+        field.is_missing = field.values.len() < objects;
+        if field.is_missing && field.first_missing.is_none() {
+            field.first_missing = Some(field.values.len());
+        }
+        field.is_nullable = field.values.iter().any(|value| value.is_null());
+        field.is_optional = field.is_missing || field.is_nullable;
+        field.is_always_null = field.values.iter().all(|value| value.is_null());
+        if options.all_required {
+            field.is_missing = false;
+            field.is_nullable = false;
+            field.is_optional = false;
+        } else if options.all_optional {
+            field.is_missing = true;
+            field.is_optional = true;
+        }
+    }
+    if !options.preserve_order {
+        fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+    }
+    fields
+}
+
+/// Collects the elements of every array in `values` into a single list.
+pub(crate) fn array_elements<'a>(values: &[&'a Value]) -> Vec<&'a Value> {
+    values
+        .iter()
+        .filter_map(|value| value.as_array())
+        .flatten()
+        .collect()
+}
+
+/// Unifies the types `value_type` maps `values` to, for the emitters that type a field
+/// from its values alone. Integers mixed with floats take the float type, and values
+/// that can't share a type give `None`, as do values that are all `null`.
+pub(crate) fn unify_value_types(
+    values: &[&Value],
+    value_type: impl Fn(&Value) -> &'static str,
+) -> Option<&'static str> {
+    let types: BTreeSet<&str> = values.iter().map(|value| value_type(value)).collect();
+    let float = value_type(&Value::from(0.5));
+    let integers_and_floats = BTreeSet::from([value_type(&Value::from(0)), float]);
+    match types.iter().copied().collect::<Vec<&str>>().as_slice() {
+        [ty] if *ty != value_type(&Value::Null) => Some(ty),
+        _ if types == integers_and_floats => Some(float),
+        _ => None,
+    }
+}
+
+/// Types a field from its observed `values`: arrays become `list` of the unified type
+/// of their elements, and anything else the unified type of the non-null values, with
+/// `unknown` standing in when there is no single type.
+pub(crate) fn value_field_type(
+    values: &[&Value],
+    value_type: impl Fn(&Value) -> &'static str,
+    unknown: &str,
+    list: impl Fn(&str) -> String,
+) -> String {
+    let present: Vec<&Value> = values.iter().filter(|v| !v.is_null()).copied().collect();
+    if !present.is_empty() && present.iter().all(|value| value.is_array()) {
+        let elements = array_elements(&present);
+        return list(unify_value_types(&elements, &value_type).unwrap_or(unknown));
+    }
+    unify_value_types(&present, &value_type)
+        .unwrap_or(unknown)
+        .to_string()
+}
@@ -0,0 +1,123 @@
+//! Go struct generation.
+
+use crate::fields::{merge_fields, value_field_type};
+use crate::naming::{dedupe_field_names, pascal_case};
+use crate::Options;
+use serde_json::Value;
+
+/// Prints a Go struct with exported field names and `json` tags preserving the
+/// original keys. Optional scalars become pointers and every optional field is
+/// tagged `omitempty`.
+pub fn print_go_struct(values: &[Value], options: &Options) -> String {
+    let values: Vec<&Value> = values.iter().collect();
+    let indent = options.indent.as_deref().unwrap_or("\t");
+    let mut rows = Vec::new();
+
+    let fields = merge_fields(&values, options);
+    let keys: Vec<&str> = fields.iter().map(|(key, _)| key.as_str()).collect();
+    let mut names: Vec<String> = keys.iter().map(|key| go_field_name(key)).collect();
+    dedupe_field_names(&keys, &mut names);
+
+    for ((key, field), name) in fields.iter().zip(names) {
+        let mut ty = go_field_type(&field.values);
+        let mut tag = key.replace('\\', "\\\\").replace('"', "\\\"");
+        if field.is_optional {
+            if !ty.starts_with("[]") && !ty.starts_with("map[") && ty != "interface{}" {
+                ty = format!("*{}", ty);
+            }
+            tag.push_str(",omitempty");
+        }
+        let tag = format!("json:\"{}\"", tag);
+        // A raw string literal can't hold a backquote, so such tags are quoted instead.
+        let tag = if tag.contains('`') {
+            Value::String(tag).to_string()
+        } else {
+            format!("`{}`", tag)
+        };
+        rows.push((name, ty, tag));
+    }
+
+    // Align the columns the same way gofmt would.
+    let name_width = rows.iter().map(|(n, _, _)| n.len()).max().unwrap_or(0);
+    let type_width = rows.iter().map(|(_, t, _)| t.len()).max().unwrap_or(0);
+    let mut output = String::new();
+    for (field_name, ty, tag) in rows {
+        output.push_str(&format!(
+            "{}{:name_width$} {:type_width$} {}\n",
+            indent,
+            field_name,
+            ty,
+            tag,
+            name_width = name_width,
+            type_width = type_width
+        ));
+    }
+    format!(
+        "type {} struct {{\n{}}}",
+        options.type_name(&options.name),
+        output
+    )
+}
+
+/// Converts a key to an exported field name, which `encoding/json` needs to see the
+/// field at all. Names that wouldn't start with an uppercase letter get an `X` prefix.
+fn go_field_name(key: &str) -> String {
+    let name = pascal_case(key);
+    if name.starts_with(char::is_uppercase) {
+        name
+    } else {
+        format!("X{}", name.trim_start_matches('_'))
+    }
+}
+
+fn go_field_type(values: &[&Value]) -> String {
+    value_field_type(values, go_value_type, "interface{}", |ty| {
+        format!("[]{}", ty)
+    })
+}
+
+/// Maps a single JSON value to its Go type.
+pub fn go_value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "interface{}",
+        Value::Bool(_) => "bool",
+        Value::Number(n) if n.is_f64() => "float64",
+        Value::Number(_) => "int64",
+        Value::String(_) => "string",
+        Value::Array(_) => "[]interface{}",
+        Value::Object(_) => "map[string]interface{}",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_print_go_struct() {
+        let json_value = json!([
+            { "name": "Alice", "age": 30, "tags": ["a"] },
+            { "name": "Bob", "age": 25, "is_student": true, "tags": [] }
+        ]);
+
+        let expected_output = "type Data struct {\n\tAge       int64    `json:\"age\"`\n\tIsStudent *bool    `json:\"is_student,omitempty\"`\n\tName      string   `json:\"name\"`\n\tTags      []string `json:\"tags\"`\n}";
+
+        assert_eq!(
+            print_go_struct(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_go_struct_field_names() {
+        let json_value = json!([{ "userId": 1, "user_id": 2, "1st": true, "q\"k": "a" }]);
+
+        let expected_output = "type Data struct {\n\tX1st    bool   `json:\"1st\"`\n\tQK      string `json:\"q\\\"k\"`\n\tUserId  int64  `json:\"userId\"`\n\tUserId2 int64  `json:\"user_id\"`\n}";
+
+        assert_eq!(
+            print_go_struct(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! GraphQL SDL type generation.
+
+use crate::naming::{camel_case, dedupe_field_names, pascal_case};
+use crate::schema::{infer_type, InferredField, InferredType, ScalarType};
+use crate::Options;
+use serde_json::Value;
+
+/// Custom scalar used for values whose type can't be expressed in GraphQL.
+const GRAPHQL_JSON_SCALAR: &str = "JSON";
+
+/// Accumulates the generated types, in declaration order, while walking the schema.
+struct GraphQlGenerator<'a> {
+    options: &'a Options,
+    indent: &'a str,
+    types: Vec<(String, String)>,
+    /// Set once a field falls back to the `JSON` scalar, so it can be declared.
+    uses_json_scalar: bool,
+}
+
+impl<'a> GraphQlGenerator<'a> {
+    /// Picks a type name that hasn't been generated yet by appending a counter.
+    fn unique_type_name(&self, name: &str) -> String {
+        let mut candidate = name.to_string();
+        let mut counter = 2;
+        while self
+            .types
+            .iter()
+            .any(|(existing, _)| existing == &candidate)
+        {
+            candidate = format!("{}{}", name, counter);
+            counter += 1;
+        }
+        candidate
+    }
+
+    /// Maps an inferred type to a GraphQL type reference without the trailing `!`.
+    fn type_ref(&mut self, key: &str, ty: &InferredType) -> String {
+        match ty {
+            InferredType::Scalar(scalar) => graphql_scalar_type(*scalar).to_string(),
+            // GraphQL types need at least one field.
+            InferredType::Object(fields) if fields.is_empty() => {
+                self.uses_json_scalar = true;
+                GRAPHQL_JSON_SCALAR.to_string()
+            }
+            InferredType::Object(fields) => {
+                let name = self.options.type_name(&pascal_case(key));
+                self.generate_type(&name, fields)
+            }
+            InferredType::Array(items) => {
+                let key = match **items {
+                    InferredType::Array(_) => key.to_string(),
+                    _ => self.options.element_key(key),
+                };
+                format!("[{}]", self.type_ref(&key, items))
+            }
+            InferredType::Optional(inner) => self.type_ref(key, inner),
+            InferredType::Unknown | InferredType::Union(_) => {
+                self.uses_json_scalar = true;
+                GRAPHQL_JSON_SCALAR.to_string()
+            }
+        }
+    }
+
+    /// Generates a type for an inferred object, along with any nested types it
+    /// needs. Returns the name given to the type.
+    fn generate_type(&mut self, name: &str, fields: &[InferredField]) -> String {
+        let name = self.unique_type_name(name);
+        let index = self.types.len();
+        self.types.push((name.clone(), String::new()));
+
+        let keys: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+        let mut names: Vec<String> = keys.iter().map(|key| camel_case(key)).collect();
+        dedupe_field_names(&keys, &mut names);
+
+        let mut output = String::new();
+        for (field, field_name) in fields.iter().zip(&names) {
+            let ty = self.type_ref(&field.name, &field.ty);
+            let non_null = field.required && !matches!(field.ty, InferredType::Optional(_));
+            output.push_str(&format!(
+                "{}{}: {}{}\n",
+                self.indent,
+                field_name,
+                ty,
+                if non_null { "!" } else { "" }
+            ));
+        }
+        self.types[index].1 = format!("type {} {{\n{}}}", name, output);
+        name
+    }
+}
+
+/// Prints a GraphQL `type` for the objects in `values`, followed by a type for every
+/// nested object. Required fields are non-null, and a `JSON` scalar is declared when a
+/// field's type can't be expressed otherwise. Objects without fields are typed as
+/// `JSON`, and an empty root object becomes a custom scalar of its own.
+pub fn print_graphql_type(values: &[Value], options: &Options) -> String {
+    let values: Vec<&Value> = values.iter().collect();
+    let mut generator = GraphQlGenerator {
+        options,
+        indent: options.indent.as_deref().unwrap_or("  "),
+        types: Vec::new(),
+        uses_json_scalar: false,
+    };
+    let name = options.type_name(&options.name);
+    let fields = match infer_type(&values, options) {
+        InferredType::Object(fields) => fields,
+        _ => Vec::new(),
+    };
+    if fields.is_empty() {
+        return format!("scalar {}", name);
+    }
+    generator.generate_type(&name, &fields);
+
+    let types: Vec<String> = generator.types.into_iter().map(|(_, body)| body).collect();
+    let mut output = String::new();
+    if generator.uses_json_scalar {
+        output.push_str(&format!("scalar {}\n\n", GRAPHQL_JSON_SCALAR));
+    }
+    output.push_str(&types.join("\n\n"));
+    output
+}
+
+/// Maps a scalar to its built-in GraphQL type.
+pub fn graphql_scalar_type(scalar: ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::Bool => "Boolean",
+        ScalarType::Integer => "Int",
+        ScalarType::Float => "Float",
+        ScalarType::String => "String",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_print_graphql_type() {
+        let json_value = json!([
+            { "user_name": "alice", "age": 30, "tags": ["a"], "address": { "city": "Paris" }, "meta": 1 },
+            { "user_name": "bob", "age": null, "tags": [], "meta": "x" }
+        ]);
+
+        let expected_output = "scalar JSON\n\ntype Data {\n  address: Address\n  age: Int\n  meta: JSON!\n  tags: [String]!\n  userName: String!\n}\n\ntype Address {\n  city: String!\n}";
+
+        assert_eq!(
+            print_graphql_type(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_graphql_type_empty_and_colliding_fields() {
+        let json_value = json!([{ "userId": 1, "user_id": 2, "meta": {} }]);
+
+        let expected_output =
+            "scalar JSON\n\ntype Data {\n  meta: JSON!\n  userId: Int!\n  userId2: Int!\n}";
+
+        assert_eq!(
+            print_graphql_type(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+        assert_eq!(
+            print_graphql_type(&[json!({})], &Options::default()),
+            "scalar Data"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! Asking on the terminal how to type ambiguous fields, for `--interactive`.
+
+use json_to_struct::{Ambiguity, Resolution};
+use std::io::{self, BufRead, Write};
+
+/// Asks how to type each of `ambiguities`, reading answers from `input` and writing
+/// the prompts to `prompt`. An empty answer, or the end of the input, keeps the
+/// default choice.
+pub(crate) fn resolve(
+    ambiguities: &[Ambiguity],
+    input: &mut impl BufRead,
+    prompt: &mut impl Write,
+) -> io::Result<Vec<(String, Resolution)>> {
+    let mut resolutions = Vec::new();
+    for ambiguity in ambiguities {
+        writeln!(prompt, "field `{}`: {}", ambiguity.key, ambiguity.reason)?;
+        writeln!(prompt, "  e.g. {}", ambiguity.examples.join(", "))?;
+        for (number, choice) in ambiguity.choices.iter().enumerate() {
+            writeln!(prompt, "  {}) {}", number + 1, choice)?;
+        }
+        let choice = loop {
+            write!(prompt, "choose [1]: ")?;
+            prompt.flush()?;
+            let mut answer = String::new();
+            if input.read_line(&mut answer)? == 0 {
+                writeln!(prompt)?;
+                break ambiguity.choices[0];
+            }
+            match answer.trim() {
+                "" => break ambiguity.choices[0],
+                answer => match answer.parse::<usize>() {
+                    Ok(number) if (1..=ambiguity.choices.len()).contains(&number) => {
+                        break ambiguity.choices[number - 1]
+                    }
+                    _ => writeln!(
+                        prompt,
+                        "  please enter a number from 1 to {}",
+                        ambiguity.choices.len()
+                    )?,
+                },
+            }
+        };
+        resolutions.push((ambiguity.key.clone(), choice));
+    }
+    Ok(resolutions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let ambiguity = |key: &str| Ambiguity {
+            key: key.to_string(),
+            reason: "2 distinct strings".to_string(),
+            examples: vec!["\"open\"".to_string(), "\"closed\"".to_string()],
+            choices: vec![Resolution::String, Resolution::Enum],
+        };
+        let ambiguities = [ambiguity("status"), ambiguity("kind"), ambiguity("state")];
+        let mut prompt = Vec::new();
+
+        let resolutions = resolve(&ambiguities, &mut "3\n2\n\n".as_bytes(), &mut prompt).unwrap();
+        assert_eq!(
+            resolutions,
+            [
+                ("status".to_string(), Resolution::Enum),
+                ("kind".to_string(), Resolution::String),
+                ("state".to_string(), Resolution::String),
+            ]
+        );
+        let prompt = String::from_utf8(prompt).unwrap();
+        assert!(prompt.starts_with(
+            "field `status`: 2 distinct strings\n  e.g. \"open\", \"closed\"\n  1) String\n  2) an enum of the observed values\nchoose [1]: "
+        ));
+        assert!(prompt.contains("please enter a number from 1 to 2"));
+    }
+}
@@ -0,0 +1,177 @@
+//! JSON Schema (draft-07) generation.
+
+use crate::schema::{infer_type, InferredType, ScalarType};
+use crate::Options;
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use serde_json::{json, Map, Value};
+
+/// Prints a draft-07 JSON Schema describing the objects in `values`. Properties that
+/// appear in every object are listed as `required`.
+pub fn print_json_schema(values: &[Value], options: &Options) -> String {
+    let values: Vec<&Value> = values.iter().collect();
+    let mut schema = Map::new();
+    schema.insert(
+        "$schema".to_string(),
+        json!("http://json-schema.org/draft-07/schema#"),
+    );
+    schema.insert("title".to_string(), json!(options.name));
+    if let Value::Object(root) = type_schema(&infer_type(&values, options)) {
+        schema.extend(root);
+    }
+
+    let indent = options.indent.as_deref().unwrap_or("  ");
+    let mut output = Vec::new();
+    let mut serializer = serde_json::Serializer::with_formatter(
+        &mut output,
+        PrettyFormatter::with_indent(indent.as_bytes()),
+    );
+    Value::Object(schema)
+        .serialize(&mut serializer)
+        .expect("serializing a Value can't fail");
+    String::from_utf8(output).expect("serde_json writes valid UTF-8")
+}
+
+/// Builds the schema for an inferred type, allowing `null` for optional values.
+fn type_schema(ty: &InferredType) -> Value {
+    match ty {
+        InferredType::Unknown => json!({}),
+        InferredType::Scalar(scalar) => json!({ "type": scalar_type_name(*scalar) }),
+        InferredType::Object(fields) => {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            for field in fields {
+                if field.required {
+                    required.push(json!(field.name));
+                }
+                properties.insert(field.name.clone(), type_schema(&field.ty));
+            }
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            })
+        }
+        InferredType::Array(items) if **items == InferredType::Unknown => {
+            json!({ "type": "array" })
+        }
+        InferredType::Array(items) => json!({ "type": "array", "items": type_schema(items) }),
+        InferredType::Optional(inner) => {
+            let mut schema = type_schema(inner);
+            // A union of structured types has no `type` to extend, only its `anyOf`.
+            if let Some(Value::Array(schemas)) = schema.get_mut("anyOf") {
+                schemas.push(json!({ "type": "null" }));
+                return schema;
+            }
+            schema["type"] = match schema["type"].take() {
+                Value::Null => json!("null"),
+                Value::Array(mut types) => {
+                    types.push(json!("null"));
+                    Value::Array(types)
+                }
+                ty => json!([ty, "null"]),
+            };
+            schema
+        }
+        InferredType::Union(members) => {
+            let schemas: Vec<Value> = members.iter().map(type_schema).collect();
+            // Scalars only need their type names; anything structured needs `anyOf`.
+            if members
+                .iter()
+                .all(|member| matches!(member, InferredType::Scalar(_)))
+            {
+                let types: Vec<Value> = schemas.into_iter().map(|s| s["type"].clone()).collect();
+                json!({ "type": types })
+            } else {
+                json!({ "anyOf": schemas })
+            }
+        }
+    }
+}
+
+fn scalar_type_name(scalar: ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::Bool => "boolean",
+        ScalarType::Integer => "integer",
+        ScalarType::Float => "number",
+        ScalarType::String => "string",
+    }
+}
+
+/// Maps a single JSON value to its JSON Schema type name.
+pub(crate) fn json_schema_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_print_json_schema() {
+        let json_value = json!([
+            { "name": "Alice", "age": 30, "tags": ["a"], "address": { "city": "Paris" } },
+            { "name": "Bob", "age": 25.5, "tags": [], "nickname": null }
+        ]);
+
+        let schema: Value = serde_json::from_str(&print_json_schema(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ))
+        .unwrap();
+
+        assert_eq!(
+            schema,
+            json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "title": "Data",
+                "type": "object",
+                "properties": {
+                    "address": {
+                        "type": "object",
+                        "properties": { "city": { "type": "string" } },
+                        "required": ["city"]
+                    },
+                    "age": { "type": "number" },
+                    "name": { "type": "string" },
+                    "nickname": { "type": "null" },
+                    "tags": { "type": "array", "items": { "type": "string" } }
+                },
+                "required": ["age", "name", "tags"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_print_json_schema_nullable_union() {
+        let json_value = json!([{ "a": 1 }, { "a": [1] }, { "a": null }, { "a": { "b": true } }]);
+
+        let schema: Value = serde_json::from_str(&print_json_schema(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ))
+        .unwrap();
+
+        assert_eq!(
+            schema["properties"]["a"],
+            json!({ "anyOf": [
+                { "type": "integer" },
+                { "type": "array", "items": { "type": "integer" } },
+                {
+                    "type": "object",
+                    "properties": { "b": { "type": "boolean" } },
+                    "required": ["b"]
+                },
+                { "type": "null" }
+            ] })
+        );
+    }
+}
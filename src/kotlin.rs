@@ -0,0 +1,147 @@
+//! Kotlin data class generation.
+
+use crate::fields::{merge_fields, value_field_type};
+use crate::naming::{camel_case, dedupe_field_names};
+use crate::Options;
+use serde_json::Value;
+
+/// Hard keywords that must be escaped with backticks when used as property names.
+const KOTLIN_KEYWORDS: [&str; 28] = [
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+/// Prints a `data class` whose constructor declares a property for every key. Optional
+/// properties are nullable and default to `null`, and properties whose name differs
+/// from the JSON key carry a `@SerializedName` annotation.
+pub fn print_kotlin_data_class(values: &[Value], options: &Options) -> String {
+    let values: Vec<&Value> = values.iter().collect();
+    let indent = options.indent.as_deref().unwrap_or("    ");
+    let mut params = Vec::new();
+    let fields = merge_fields(&values, options);
+    let keys: Vec<&str> = fields.iter().map(|(key, _)| key.as_str()).collect();
+    let mut names: Vec<String> = keys.iter().map(|key| camel_case(key)).collect();
+    dedupe_field_names(&keys, &mut names);
+
+    for ((key, field), mut name) in fields.iter().zip(names) {
+        let ty = kotlin_field_type(&field.values);
+        let mut param = String::new();
+        if name != *key {
+            param.push_str(&format!("@SerializedName({}) ", kotlin_string(key)));
+        }
+        if KOTLIN_KEYWORDS.contains(&name.as_str()) {
+            name = format!("`{}`", name);
+        }
+        if field.is_optional {
+            param.push_str(&format!("val {}: {}? = null", name, ty));
+        } else {
+            param.push_str(&format!("val {}: {}", name, ty));
+        }
+        params.push(format!("{}{}", indent, param));
+    }
+
+    format!(
+        "data class {}(\n{}\n)",
+        options.type_name(&options.name),
+        params.join(",\n")
+    )
+}
+
+/// Quotes a JSON key as a Kotlin string literal, escaping `$` so it isn't read as a
+/// template.
+fn kotlin_string(key: &str) -> String {
+    let mut literal = String::from('"');
+    for c in key.chars() {
+        match c {
+            '\\' | '"' | '$' => {
+                literal.push('\\');
+                literal.push(c);
+            }
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c.is_control() => literal.push_str(&format!("\\u{:04x}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+fn kotlin_field_type(values: &[&Value]) -> String {
+    value_field_type(values, kotlin_value_type, "Any", |ty| {
+        format!("List<{}>", ty)
+    })
+}
+
+/// Maps a single JSON value to its Kotlin type.
+pub fn kotlin_value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "Any",
+        Value::Bool(_) => "Boolean",
+        Value::Number(n) if n.is_f64() => "Double",
+        Value::Number(_) => "Long",
+        Value::String(_) => "String",
+        Value::Array(_) => "List<Any>",
+        Value::Object(_) => "Map<String, Any>",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_print_kotlin_data_class() {
+        let json_value = json!([
+            { "first_name": "Alice", "age": 30, "scores": [1.5, 2], "class": "A" },
+            { "first_name": "Bob", "age": 25, "scores": [], "is_student": true, "class": "B" }
+        ]);
+
+        let expected_output = "data class Data(\n    val age: Long,\n    val `class`: String,\n    @SerializedName(\"first_name\") val firstName: String,\n    @SerializedName(\"is_student\") val isStudent: Boolean? = null,\n    val scores: List<Double>\n)";
+
+        assert_eq!(
+            print_kotlin_data_class(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_kotlin_data_class_renamed_properties() {
+        let json_value = json!([{ "$type": "a", "userId": 1, "user_id": 2, "q\"k": true }]);
+
+        let expected_output = "data class Data(\n    @SerializedName(\"\\$type\") val type: String,\n    @SerializedName(\"q\\\"k\") val qK: Boolean,\n    val userId: Long,\n    @SerializedName(\"user_id\") val userId2: Long\n)";
+
+        assert_eq!(
+            print_kotlin_data_class(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+}
@@ -0,0 +1,380 @@
+//! Generates type definitions, such as Rust structs, TypeScript interfaces, Python
+//! dataclasses, Go structs, Kotlin data classes, JSON Schemas, C# classes, SQL tables,
+//! Protobuf messages, GraphQL types, Swift structs, Dart classes or OpenAPI schema
+//! components, that represent a list of sample JSON objects.
+//!
+//! ```
+//! use json_to_struct::{generate, Language, Options};
+//! use serde_json::json;
+//!
+//! let values = json!([{ "name": "Alice" }, { "name": "Bob", "age": 25 }]);
+//! let output = generate(values.as_array().unwrap(), Language::Rust, &Options::default());
+//! assert_eq!(output, "struct Data {\n    age: Option<i64>,\n    name: String,\n}");
+//! ```
+//!
+//! [`infer_schema`] returns the inferred shape as an [`InferredType`] tree instead of
+//! code. Only the JSON Schema, GraphQL and OpenAPI generators are built on it. The
+//! others type each field from the sample values directly, since they rely on details
+//! the tree doesn't record, such as string formats, number ranges and the distinct
+//! values of a field.
+
+mod ambiguity;
+mod csharp;
+mod dart;
+mod diff;
+mod fields;
+mod go;
+mod graphql;
+mod jsonschema;
+mod kotlin;
+mod naming;
+mod openapi;
+mod proto;
+mod python;
+mod rust;
+mod schema;
+mod sql;
+mod stats;
+mod swift;
+mod typescript;
+
+pub use ambiguity::{find_ambiguities, Ambiguity};
+pub use csharp::{csharp_value_type, print_csharp_class};
+pub use dart::{dart_value_type, print_dart_class};
+pub use diff::diff_schemas;
+pub use go::{go_value_type, print_go_struct};
+pub use graphql::{graphql_scalar_type, print_graphql_type};
+pub use jsonschema::print_json_schema;
+pub use kotlin::{kotlin_value_type, print_kotlin_data_class};
+pub use openapi::{openapi_scalar_type, print_openapi_components};
+pub use proto::{print_proto_message, proto_value_type};
+pub use python::{print_python_dataclass, python_value_type};
+pub use rust::{
+    inexact_numbers, precision_warnings, print_rust_struct, rust_value_type, NullPolicy,
+    Resolution, RustOptions, SharedPointer, TagStyle,
+};
+pub use schema::{infer_schema, infer_schema_with, InferredField, InferredType, ScalarType};
+pub use sql::{print_sql_table, sql_value_type};
+pub use stats::print_stats;
+pub use swift::{print_swift_struct, swift_value_type};
+pub use typescript::{
+    print_typescript_interface, typescript_value_type, TsNullStyle, TypeScriptOptions,
+};
+
+use naming::{pascal_case, singularize};
+use serde_json::Value;
+use std::str::FromStr;
+
+/// The output formats the generator supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    TypeScript,
+    Python,
+    Go,
+    Kotlin,
+    JsonSchema,
+    CSharp,
+    Sql,
+    Proto,
+    GraphQl,
+    Swift,
+    Dart,
+    OpenApi,
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rust" => Ok(Language::Rust),
+            "typescript" => Ok(Language::TypeScript),
+            "python" => Ok(Language::Python),
+            "go" => Ok(Language::Go),
+            "kotlin" => Ok(Language::Kotlin),
+            "jsonschema" => Ok(Language::JsonSchema),
+            "csharp" => Ok(Language::CSharp),
+            "sql" => Ok(Language::Sql),
+            "proto" => Ok(Language::Proto),
+            "graphql" => Ok(Language::GraphQl),
+            "swift" => Ok(Language::Swift),
+            "dart" => Ok(Language::Dart),
+            "openapi" => Ok(Language::OpenApi),
+            _ => Err(format!(
+                "Invalid format '{}'. Please use 'rust', 'typescript', 'python', 'go', 'kotlin', \
+                 'jsonschema', 'csharp', 'sql', 'proto', 'graphql', 'swift', 'dart' or 'openapi'.",
+                s
+            )),
+        }
+    }
+}
+
+impl Language {
+    /// Picks the output format conventionally written to files with the extension
+    /// `ext`, such as `rs` or `ts`, if there is one.
+    pub fn from_extension(ext: &str) -> Option<Language> {
+        match ext {
+            "rs" => Some(Language::Rust),
+            "ts" => Some(Language::TypeScript),
+            "py" => Some(Language::Python),
+            "go" => Some(Language::Go),
+            "kt" => Some(Language::Kotlin),
+            "cs" => Some(Language::CSharp),
+            "sql" => Some(Language::Sql),
+            "proto" => Some(Language::Proto),
+            "graphql" | "gql" => Some(Language::GraphQl),
+            "swift" => Some(Language::Swift),
+            "dart" => Some(Language::Dart),
+            "yaml" | "yml" => Some(Language::OpenApi),
+            _ => None,
+        }
+    }
+}
+
+/// Options shared by every output format, plus the options specific to Rust and
+/// TypeScript.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Name of the root type. Defaults to `Data`.
+    pub name: String,
+    /// Indentation for each nesting level. Defaults to the language's convention:
+    /// a tab for Go, two spaces for Protobuf, GraphQL and Dart, and four spaces everywhere
+    /// else.
+    pub indent: Option<String>,
+    /// Keeps fields in the order they first appear instead of sorting them by key.
+    pub preserve_order: bool,
+    /// Prepended to the name of every generated type, including the root.
+    pub name_prefix: String,
+    /// Appended to the name of every generated type, including the root.
+    pub name_suffix: String,
+    /// Deepest level of nesting, counted from the root object at 0, that gets its own
+    /// type. Objects below it fall back to a dynamic type such as Rust's `Value`.
+    pub max_depth: Option<usize>,
+    /// Names the types of array elements after the singular of the key, e.g. `User`
+    /// for `users`.
+    pub singularize: bool,
+    /// Treats every field as required, even when it is missing or `null` in some of
+    /// the objects.
+    pub all_required: bool,
+    /// Treats every field as optional, even when it is present in all the objects.
+    pub all_optional: bool,
+    /// Prints more compactly: TypeScript interfaces with at most this many fields go
+    /// on a single line, and Rust types aren't separated by blank lines.
+    pub compact: Option<usize>,
+    pub rust: RustOptions,
+    pub typescript: TypeScriptOptions,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            name: "Data".to_string(),
+            indent: None,
+            preserve_order: false,
+            name_prefix: String::new(),
+            name_suffix: String::new(),
+            max_depth: None,
+            singularize: true,
+            all_required: false,
+            all_optional: false,
+            compact: None,
+            rust: RustOptions::default(),
+            typescript: TypeScriptOptions::default(),
+        }
+    }
+}
+
+impl Options {
+    /// Applies the name prefix and suffix to a generated type name.
+    pub(crate) fn type_name(&self, name: &str) -> String {
+        format!("{}{}{}", self.name_prefix, name, self.name_suffix)
+    }
+
+    /// Returns the key to name the elements of the array under `key` after.
+    pub(crate) fn element_key(&self, key: &str) -> String {
+        if self.singularize {
+            singularize(key)
+        } else {
+            key.to_string()
+        }
+    }
+}
+
+/// Parses an `--indent` value: a number of spaces or `tab`.
+pub fn parse_indent(s: &str) -> Result<String, String> {
+    match s {
+        "tab" => Ok("\t".to_string()),
+        _ => s
+            .parse::<usize>()
+            .map(|spaces| " ".repeat(spaces))
+            .map_err(|_| format!("Invalid indent '{}'. Use a number of spaces or 'tab'.", s)),
+    }
+}
+
+/// Generates the type definitions for `values` in the given language.
+pub fn generate(values: &[Value], language: Language, options: &Options) -> String {
+    match language {
+        Language::Rust => print_rust_struct(values, options),
+        Language::TypeScript => print_typescript_interface(values, options),
+        Language::Python => print_python_dataclass(values, options),
+        Language::Go => print_go_struct(values, options),
+        Language::Kotlin => print_kotlin_data_class(values, options),
+        Language::JsonSchema => print_json_schema(values, options),
+        Language::CSharp => print_csharp_class(values, options),
+        Language::Sql => print_sql_table(values, options),
+        Language::Proto => print_proto_message(values, options),
+        Language::GraphQl => print_graphql_type(values, options),
+        Language::Swift => print_swift_struct(values, options),
+        Language::Dart => print_dart_class(values, options),
+        Language::OpenApi => print_openapi_components(values, options),
+    }
+}
+
+/// Generates a root type per collection for a document such as
+/// `{"users": [...], "orders": [...]}`: each top-level key holding an array of objects
+/// becomes its own type, named after the singular of the key (`User`, `Order`). The
+/// arrays of every object in `values` are merged by key, and other top-level fields
+/// are ignored.
+pub fn generate_collections(values: &[Value], language: Language, options: &Options) -> String {
+    let mut collections: Vec<(&String, Vec<Value>)> = Vec::new();
+    for (key, value) in values.iter().filter_map(Value::as_object).flatten() {
+        let elements = match value.as_array() {
+            Some(elements) if elements.iter().any(Value::is_object) => elements,
+            _ => continue,
+        };
+        match collections
+            .iter_mut()
+            .find(|(existing, _)| *existing == key)
+        {
+            Some((_, merged)) => merged.extend(elements.iter().cloned()),
+            None => collections.push((key, elements.clone())),
+        }
+    }
+
+    collections
+        .iter()
+        .map(|(key, elements)| {
+            let options = Options {
+                name: pascal_case(&options.element_key(key)),
+                ..options.clone()
+            };
+            generate(elements, language, &options)
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+/// Strips indentation and normalises trailing commas to semicolons so expected
+/// outputs can be written compactly in tests.
+#[cfg(test)]
+pub(crate) fn format_output(s: &str) -> String {
+    s.lines()
+        .map(|line| {
+            let line = line.trim_start();
+            if line.ends_with(',') {
+                let len = line.len();
+                format!("{};", &line[0..len - 1])
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_generate_uses_root_name() {
+        let json_value = json!([{ "name": "Alice" }]);
+        let options = Options {
+            name: "User".to_string(),
+            ..Options::default()
+        };
+
+        let output = generate(
+            json_value.as_array().unwrap(),
+            Language::TypeScript,
+            &options,
+        );
+        assert_eq!(output, "interface User {\n    name: string;\n}");
+        assert_eq!("go".parse::<Language>(), Ok(Language::Go));
+        assert!("java".parse::<Language>().is_err());
+        assert_eq!(Language::from_extension("ts"), Some(Language::TypeScript));
+        assert_eq!(Language::from_extension("json"), None);
+    }
+
+    #[test]
+    fn test_generate_collections() {
+        let json_value = json!([{
+            "users": [{ "name": "Alice" }],
+            "order_items": [{ "id": 1 }],
+            "tags": ["a"],
+            "total": 2
+        }]);
+
+        let output = generate_collections(
+            json_value.as_array().unwrap(),
+            Language::TypeScript,
+            &Options::default(),
+        );
+        assert_eq!(
+            output,
+            "interface User {\n    name: string;\n}\n\ninterface OrderItem {\n    id: number;\n}"
+        );
+    }
+
+    #[test]
+    fn test_generate_collections_reserved_names() {
+        let json_value = json!([{ "values": [{ "a": 1 }], "options": [{ "b": 2 }] }]);
+
+        let output = generate_collections(
+            json_value.as_array().unwrap(),
+            Language::Rust,
+            &Options::default(),
+        );
+        assert_eq!(
+            output,
+            "struct Value2 {\n    a: i64,\n}\n\nstruct Option2 {\n    b: i64,\n}"
+        );
+    }
+
+    #[test]
+    fn test_generate_with_indent() {
+        let json_value = json!([{ "address": { "city": "Paris" } }]);
+        let options = Options {
+            indent: Some(parse_indent("2").unwrap()),
+            ..Options::default()
+        };
+
+        let output = generate(json_value.as_array().unwrap(), Language::Rust, &options);
+        assert_eq!(
+            output,
+            "struct Data {\n  address: Address,\n}\n\nstruct Address {\n  city: String,\n}"
+        );
+        assert_eq!(parse_indent("tab"), Ok("\t".to_string()));
+        assert!(parse_indent("wide").is_err());
+    }
+
+    #[test]
+    fn test_generate_preserve_order() {
+        let json_values: Vec<Value> = serde_json::from_str(
+            r#"[{"name": "Alice", "age": 30}, {"zip": "0150", "name": "Bob"}]"#,
+        )
+        .unwrap();
+        let options = Options {
+            preserve_order: true,
+            ..Options::default()
+        };
+
+        let output = generate(&json_values, Language::TypeScript, &options);
+        assert_eq!(
+            output,
+            "interface Data {\n    name: string;\n    age?: number;\n    zip?: string;\n}"
+        );
+    }
+}
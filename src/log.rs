@@ -0,0 +1,48 @@
+//! Notes printed to stderr alongside the generated code, filtered by `--quiet` and
+//! `--verbose`. Errors are always printed and don't go through here.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much the tool prints to stderr besides errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Level {
+    /// Nothing but errors.
+    Quiet = 0,
+    /// Warnings and progress notes, such as `--watch` regenerations.
+    Normal = 1,
+    /// Also details about what was read and inferred.
+    Verbose = 2,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Normal as u8);
+
+/// Sets the level for the rest of the run.
+pub(crate) fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Whether messages at `level` are printed.
+pub(crate) fn enabled(level: Level) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Prints `warning: message` unless `--quiet` is given.
+pub(crate) fn warning(message: &str) {
+    if enabled(Level::Normal) {
+        eprintln!("warning: {}", message);
+    }
+}
+
+/// Prints a progress note unless `--quiet` is given.
+pub(crate) fn note(message: &str) {
+    if enabled(Level::Normal) {
+        eprintln!("{}", message);
+    }
+}
+
+/// Prints an inference detail only with `--verbose`.
+pub(crate) fn verbose(message: &str) {
+    if enabled(Level::Verbose) {
+        eprintln!("{}", message);
+    }
+}
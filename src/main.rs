@@ -1,246 +1,1337 @@
-//! A CLI tool that reads a JSON file and creates a text output of a Rust structs or
-//! TypeScript interfaces that represent the objects from the JSON file.
-//! It takes in a file path and a flag to specify whether to print out a TypeScript
-//! interface or Rust struct.
+//! A CLI tool that reads JSON files and generates type definitions, such as Rust
+//! structs, TypeScript interfaces or Python dataclasses, that represent the objects
+//! in them. It takes in one or more file paths (or `-` for stdin) and the output
+//! format; run it with `--help` for the full list of options.
 
+mod config;
+mod duplicates;
+mod interactive;
+mod log;
+mod stream;
+mod watch;
+
+use chrono::Local;
+use config::Config;
+use flate2::read::GzDecoder;
+use json_to_struct::{
+    diff_schemas, find_ambiguities, generate, generate_collections, inexact_numbers,
+    infer_schema_with, parse_indent, precision_warnings, print_stats, InferredType, Language,
+    NullPolicy, Options, SharedPointer, TagStyle, TsNullStyle,
+};
 use serde_json::Value;
-use std::collections::BTreeMap;
 use std::env;
+use std::fmt;
 use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+use structopt::clap::ErrorKind;
+use structopt::StructOpt;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+/// Everything that can stop the tool before it prints any output.
+#[derive(Debug)]
+enum Error {
+    Usage(String),
+    FileNotFound(String),
+    Read(String, io::Error),
+    Encoding(String),
+    Parse(serde_json::Error),
+    ParseYaml(serde_yaml::Error),
+    ParseToml(toml::de::Error),
+    ParseLine(usize, serde_json::Error),
+    Write(String, io::Error),
+    OutOfDate(String),
+    InvalidInput(String),
+    EmptyInput,
+    Config(String, String),
+    Watch(notify::Error),
+}
 
-    if args.len() < 3 {
-        println!("Usage: {} <file_path> <rust|typescript>", args[0]);
-        return;
+impl Error {
+    fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => 2,
+            Error::EmptyInput => 3,
+            _ => 1,
+        }
     }
+}
 
-    let file_path = &args[1];
-    let format = &args[2];
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Usage(message) => write!(f, "{}", message),
+            Error::FileNotFound(path) => write!(f, "file not found: {}", path),
+            Error::Read(path, err) => write!(f, "could not read {}: {}", path, err),
+            Error::Encoding(path) => write!(
+                f,
+                "{} is not valid UTF-8; convert it first, e.g. with `iconv -f UTF-16 -t UTF-8`",
+                path
+            ),
+            Error::Parse(err) => write!(f, "invalid JSON: {}", err),
+            Error::ParseYaml(err) => write!(f, "invalid YAML: {}", err),
+            Error::ParseToml(err) => write!(f, "invalid TOML: {}", err),
+            Error::ParseLine(line, err) => write!(f, "invalid JSON on line {}: {}", line, err),
+            Error::Write(path, err) => write!(f, "could not write {}: {}", path, err),
+            Error::OutOfDate(path) => write!(
+                f,
+                "{} is out of date with the input; regenerate it with `--out {}`",
+                path, path
+            ),
+            Error::InvalidInput(message) => write!(f, "{}", message),
+            Error::EmptyInput => write!(
+                f,
+                "input array is empty; no fields to infer (use --allow-empty to generate an empty type)"
+            ),
+            Error::Config(path, message) => write!(f, "invalid config {}: {}", path, message),
+            Error::Watch(err) => write!(f, "could not watch the input files: {}", err),
+        }
+    }
+}
 
-    let content = fs::read_to_string(file_path).expect("Failed to read file");
-    let json_value: Value = serde_json::from_str(&content).expect("Failed to parse JSON");
+fn main() {
+    if let Err(err) = run() {
+        match err {
+            Error::Usage(_) => eprintln!("{}", err),
+            _ => eprintln!("error: {}", err),
+        }
+        process::exit(err.exit_code());
+    }
+}
 
-    if !json_value.is_array() {
-        println!("The input JSON file should contain an array of objects.");
-        return;
+/// Generates type definitions from sample JSON.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "json-to-struct")]
+struct Args {
+    /// JSON, YAML or TOML files to read, or `-` for stdin, followed by the output
+    /// format: rust, typescript, python, go, kotlin, jsonschema, csharp, sql, proto,
+    /// graphql, swift, dart or openapi. The format can be left out when given with --format or set
+    /// by the config file. Objects from every file are merged
+    #[structopt(name = "INPUT", required = true)]
+    inputs: Vec<String>,
+    /// Only print errors to stderr, no warnings or progress notes
+    #[structopt(short, long, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Also print what was read and inferred to stderr
+    #[structopt(short, long)]
+    verbose: bool,
+    /// Output format, instead of giving it after the input files
+    #[structopt(short, long, name = "FORMAT")]
+    format: Option<Language>,
+    /// Config file with default flags [default: json-to-struct.toml if it exists]
+    #[structopt(long = "config", name = "CONFIG")]
+    config_path: Option<String>,
+    /// Name of the generated root type [default: Data]
+    #[structopt(long)]
+    name: Option<String>,
+    /// Prepended to the name of every generated type
+    #[structopt(long, name = "PREFIX")]
+    name_prefix: Option<String>,
+    /// Appended to the name of every generated type
+    #[structopt(long, name = "SUFFIX")]
+    name_suffix: Option<String>,
+    /// Type objects nested deeper than DEPTH levels below the root as `Value`/`unknown`
+    #[structopt(long, name = "DEPTH")]
+    max_depth: Option<usize>,
+    /// Name array element types after the key as is, e.g. `Users` rather than `User`
+    #[structopt(long)]
+    no_singularize: bool,
+    /// Make every field required, even if it is missing or null in some objects
+    #[structopt(long)]
+    all_required: bool,
+    /// Make every field optional, even if it is present in all objects
+    #[structopt(long, conflicts_with = "all-required")]
+    all_optional: bool,
+    /// Put small TypeScript interfaces on one line and drop the blank lines between
+    /// Rust types
+    #[structopt(long)]
+    compact: bool,
+    /// Most fields a TypeScript interface can have to fit on one line with --compact
+    /// [default: 4]
+    #[structopt(long, name = "MAX_FIELDS")]
+    compact_max_fields: Option<usize>,
+    /// Write the output to a file instead of stdout
+    #[structopt(long = "out", name = "PATH")]
+    out_path: Option<String>,
+    /// Fail if this file doesn't match the output, instead of writing or printing it
+    #[structopt(long = "check", name = "CHECK_PATH", conflicts_with_all = &["PATH", "clipboard"])]
+    check_path: Option<String>,
+    /// Make --check compare the file byte for byte instead of ignoring whitespace
+    #[structopt(long, requires = "CHECK_PATH")]
+    check_exact: bool,
+    /// Copy the output to the system clipboard instead of printing it
+    #[structopt(long, conflicts_with = "PATH")]
+    clipboard: bool,
+    /// Indentation per nesting level: a number of spaces or `tab`
+    #[structopt(long, parse(try_from_str = parse_indent))]
+    indent: Option<String>,
+    /// Read newline-delimited JSON (the default for .jsonl and .ndjson files)
+    #[structopt(long)]
+    ndjson: bool,
+    /// Generate a type per top-level array of objects, e.g. `User` for "users"
+    #[structopt(long)]
+    collections: bool,
+    /// Print the inferred type tree in this format (json) instead of code
+    #[structopt(long, name = "IR_FORMAT", possible_values = &["json"])]
+    emit_ir: Option<String>,
+    /// Compare the schemas inferred from two inputs, old then new, instead of
+    /// generating code
+    #[structopt(long, conflicts_with_all = &["watch", "IR_FORMAT"])]
+    diff: bool,
+    /// Generate an empty type for empty input instead of failing
+    #[structopt(long)]
+    allow_empty: bool,
+    /// Only infer types from the first COUNT objects [default: all of them]
+    #[structopt(long, name = "COUNT", parse(try_from_str = parse_sample))]
+    sample: Option<usize>,
+    /// Parse JSON and NDJSON input one object at a time to save memory
+    #[structopt(long, conflicts_with_all = &["KEY_PATH", "stats", "trace-optional"])]
+    stream: bool,
+    /// Dotted path to the array or object to read from each input, e.g. data.items
+    #[structopt(long, name = "KEY_PATH")]
+    root_path: Option<String>,
+    /// Input format: json, ndjson, yaml or toml [default: from the file extension]
+    #[structopt(long, name = "INPUT_FORMAT")]
+    input_format: Option<InputFormat>,
+    /// Decompress gzip input (the default for .gz files)
+    #[structopt(long)]
+    gzip: bool,
+    /// Keep fields in input order instead of sorting them
+    #[structopt(long)]
+    preserve_order: bool,
+    /// Regenerate the output whenever an input file changes
+    #[structopt(long)]
+    watch: bool,
+    /// Ask on the terminal how to type Rust fields with ambiguous values
+    #[structopt(long, conflicts_with = "watch")]
+    interactive: bool,
+    /// Print a summary of the observed fields to stderr
+    #[structopt(long)]
+    stats: bool,
+    /// TypeScript optional properties: optional (`?`), union (`| null`) or both
+    #[structopt(long, name = "STYLE")]
+    ts_null_style: Option<TsNullStyle>,
+    /// Rust `Option`s for nulls: optional (missing or null), value (only missing) or
+    /// strict (`Option<Option<T>>` when both happen) [default: optional]
+    #[structopt(long, name = "POLICY")]
+    null_policy: Option<NullPolicy>,
+    /// Rust derives: `serde` or a comma-separated list
+    #[structopt(long = "derive", name = "DERIVES")]
+    derives: Option<String>,
+    /// Make Rust structs and fields `pub`
+    #[structopt(long = "pub")]
+    public: bool,
+    /// Generate untagged enums for Rust fields with conflicting types
+    #[structopt(long)]
+    conflict_enums: bool,
+    /// Type date and date-time strings with chrono
+    #[structopt(long)]
+    chrono: bool,
+    /// Type UUID strings as `uuid::Uuid`
+    #[structopt(long)]
+    uuid: bool,
+    /// Type URL strings as `url::Url`
+    #[structopt(long)]
+    smart_strings: bool,
+    /// Generate a Rust struct per distinct object shape, united by an enum
+    #[structopt(long)]
+    split_shapes: bool,
+    /// How the --split-shapes enum is tagged: internal (by a discriminator key) or
+    /// untagged (by the set of keys) [default: internal]
+    #[structopt(long, name = "TAG_STYLE")]
+    tag_style: Option<TagStyle>,
+    /// Discriminator key of the --split-shapes enum [default: type, kind, ... if found]
+    #[structopt(long, name = "TAG_KEY")]
+    tag_key: Option<String>,
+    /// Add `#[non_exhaustive]` to every generated Rust struct
+    #[structopt(long)]
+    non_exhaustive: bool,
+    /// Wrap the generated Rust code in `pub mod NAME { ... }`
+    #[structopt(long, name = "MODULE")]
+    module: Option<String>,
+    /// Implement `TryFrom<&Value>` for every generated Rust struct
+    #[structopt(long)]
+    tryfrom: bool,
+    /// Append a Rust test that deserializes the first sample object
+    #[structopt(long)]
+    emit_tests: bool,
+    /// Start the Rust output with the `use` declarations it needs
+    #[structopt(long)]
+    with_imports: bool,
+    /// Derive or implement `Default` for every generated Rust struct
+    #[structopt(long)]
+    default: bool,
+    /// Type base64 strings as `Vec<u8>` decoded with `#[serde(with = "base64")]`
+    #[structopt(long)]
+    decode_base64: bool,
+    /// Type Rust string fields that all hold numbers, e.g. "30", as numbers
+    #[structopt(long)]
+    coerce_numeric_strings: bool,
+    /// Wrap Rust fields whose keys end in an ID suffix in a newtype, e.g. `UserId(i64)`
+    #[structopt(long)]
+    newtypes: bool,
+    /// Comma-separated key suffixes that --newtypes applies to [default: _id,Id]
+    #[structopt(long, name = "SUFFIXES")]
+    newtype_suffixes: Option<String>,
+    /// Type nested Rust objects as `HashMap<String, Value>` and arrays as `Vec<Value>`
+    #[structopt(long)]
+    flat: bool,
+    /// Wrap nested Rust structs in a shared pointer: rc or arc
+    #[structopt(long, name = "POINTER")]
+    shared: Option<SharedPointer>,
+    /// Comment every optional Rust field with the first object it was missing from
+    #[structopt(long)]
+    trace_optional: bool,
+    /// Comma-separated `key=Type` pairs forcing the Rust type of fields, e.g. price=Decimal
+    #[structopt(long, name = "OVERRIDES")]
+    type_override: Option<String>,
+    /// Skip serializing `None` for optional Rust fields
+    #[structopt(long)]
+    serde_skip_none: bool,
+    /// Type Rust integers as `i32` when every value fits
+    #[structopt(long)]
+    narrow_ints: bool,
+    /// Type Rust floats as `f32` when every value survives the conversion
+    #[structopt(long)]
+    prefer_f32: bool,
+    /// Add a doc comment with an example value to every Rust field
+    #[structopt(long)]
+    with_examples: bool,
+    /// Type integers too large for i64/u64 as `rust_decimal::Decimal`
+    #[structopt(long)]
+    decimal: bool,
+    /// Capture unknown keys in a `#[serde(flatten)]` map on every Rust struct
+    #[structopt(long)]
+    with_extra: bool,
+    /// Add `#[serde(deny_unknown_fields)]` to every Rust struct
+    #[structopt(long, conflicts_with = "with-extra")]
+    deny_unknown: bool,
+    /// Keep JSON keys that are valid identifiers as Rust field names
+    #[structopt(long)]
+    keep_original_names: bool,
+    /// Use one `#[serde(rename_all)]` per Rust struct when all keys share a casing
+    #[structopt(long)]
+    rename_all: bool,
+    /// Type nested objects with at least N keys and uniform values as a HashMap
+    #[structopt(long, name = "KEYS")]
+    map_threshold: Option<usize>,
+    /// Generate a builder with fluent setters for every Rust struct
+    #[structopt(long)]
+    builder: bool,
+    /// Generate a `pub` getter for every field of every Rust struct
+    #[structopt(long)]
+    accessors: bool,
+    /// Generate Rust enums for string fields with few distinct values
+    #[structopt(long)]
+    infer_enums: bool,
+    /// Most distinct values a string field can have for --infer-enums [default: 5]
+    #[structopt(long, name = "N")]
+    max_enum_variants: Option<usize>,
+}
+
+impl Args {
+    /// Splits the positional arguments into the input files and the output format.
+    /// With `--format` every positional argument is an input file; without it the
+    /// format is the last argument, or is inferred from the `--out` file's extension,
+    /// or comes from the config file.
+    fn split_inputs(&self, config: &Config) -> Result<(&[String], Language), Error> {
+        if let Some(language) = self.format {
+            return Ok((&self.inputs, language));
+        }
+        let (last, files) = self
+            .inputs
+            .split_last()
+            .expect("structopt requires at least one input");
+        let out_language = self
+            .out_path
+            .as_deref()
+            .or(self.check_path.as_deref())
+            .and_then(|path| Path::new(path).extension())
+            .and_then(|ext| ext.to_str())
+            .and_then(Language::from_extension);
+        match (last.parse::<Language>(), out_language, &config.format) {
+            (Ok(language), _, _) if !files.is_empty() => Ok((files, language)),
+            (Err(_), Some(language), _) => Ok((&self.inputs, language)),
+            (_, _, Some(format)) => {
+                let language = format.parse().map_err(|err| self.config_error(err))?;
+                Ok((&self.inputs, language))
+            }
+            // The IR and the diff are the same whichever format is given, so none is needed.
+            (Err(_), _, None) if self.emit_ir.is_some() || self.diff => {
+                Ok((&self.inputs, Language::Rust))
+            }
+            (Ok(_), _, None) => Err(Error::Usage(
+                "error: Missing input file. Use a file path or `-` for stdin.".to_string(),
+            )),
+            (Err(err), _, None) => Err(Error::Usage(format!("error: {}", err))),
+        }
     }
 
-    let json_array = json_value.as_array().unwrap();
+    fn config_error(&self, message: String) -> Error {
+        let path = self.config_path.as_deref().unwrap_or(CONFIG_FILE);
+        Error::Config(path.to_string(), message)
+    }
 
-    let output = match format.as_str() {
-        "rust" => {
-            let struct_fields = print_rust_struct(json_array);
-            format!("struct Data {{\n{}}}", struct_fields)
+    /// Builds the generator options from the flags, falling back to `config` for
+    /// anything not given on the command line.
+    fn options(&self, config: &Config) -> Result<Options, Error> {
+        let mut options = Options::default();
+        if let Some(name) = self.name.as_ref().or(config.name.as_ref()) {
+            options.name = name.clone();
         }
-        "typescript" => {
-            let interface_fields = print_typescript_interface(json_array);
-            format!("interface Data {{\n{}}}", interface_fields)
+        options.indent = match (&self.indent, &config.indent) {
+            (Some(indent), _) => Some(indent.clone()),
+            (None, Some(indent)) => {
+                Some(parse_indent(&indent.as_arg()).map_err(|err| self.config_error(err))?)
+            }
+            (None, None) => None,
+        };
+        options.preserve_order = self.preserve_order || config.preserve_order;
+        options.name_prefix = self
+            .name_prefix
+            .clone()
+            .or_else(|| config.name_prefix.clone())
+            .unwrap_or_default();
+        options.name_suffix = self
+            .name_suffix
+            .clone()
+            .or_else(|| config.name_suffix.clone())
+            .unwrap_or_default();
+        options.max_depth = self.max_depth.or(config.max_depth);
+        options.singularize = !(self.no_singularize || config.no_singularize);
+        if config.all_required && config.all_optional {
+            return Err(
+                self.config_error("all-required and all-optional can't both be set".to_string())
+            );
         }
-        _ => {
-            println!("Invalid format. Please use 'rust' or 'typescript'.");
-            return;
+        // A flag on the command line overrides the opposite one from the config file.
+        options.all_required = self.all_required || (config.all_required && !self.all_optional);
+        options.all_optional = self.all_optional || (config.all_optional && !self.all_required);
+        options.compact = (self.compact || config.compact).then_some(
+            self.compact_max_fields
+                .or(config.compact_max_fields)
+                .unwrap_or(4),
+        );
+        options.typescript.null_style = match (self.ts_null_style, &config.ts_null_style) {
+            (Some(style), _) => style,
+            (None, Some(style)) => style.parse().map_err(|err| self.config_error(err))?,
+            (None, None) => TsNullStyle::default(),
+        };
+        if let Some(derives) = self.derives.as_ref().or(config.derive.as_ref()) {
+            options.rust.set_derives(derives);
+        }
+        options.rust.public = self.public || config.public;
+        options.rust.conflict_enums = self.conflict_enums || config.conflict_enums;
+        options.rust.chrono = self.chrono || config.chrono;
+        options.rust.uuid = self.uuid || config.uuid;
+        options.rust.skip_none = self.serde_skip_none || config.serde_skip_none;
+        options.rust.narrow_ints = self.narrow_ints || config.narrow_ints;
+        options.rust.prefer_f32 = self.prefer_f32 || config.prefer_f32;
+        options.rust.examples = self.with_examples || config.with_examples;
+        options.rust.builder = self.builder || config.builder;
+        options.rust.accessors = self.accessors || config.accessors;
+        if config.with_extra && config.deny_unknown {
+            return Err(
+                self.config_error("with-extra and deny-unknown can't both be set".to_string())
+            );
+        }
+        options.rust.extra = self.with_extra || (config.with_extra && !self.deny_unknown);
+        options.rust.deny_unknown = self.deny_unknown || (config.deny_unknown && !self.with_extra);
+        options.rust.map_threshold = self.map_threshold.or(config.map_threshold);
+        options.rust.keep_names = self.keep_original_names || config.keep_original_names;
+        options.rust.rename_all = self.rename_all || config.rename_all;
+        options.rust.smart_strings = self.smart_strings || config.smart_strings;
+        options.rust.split_shapes = self.split_shapes || config.split_shapes;
+        options.rust.tag_style = match (self.tag_style, &config.tag_style) {
+            (Some(style), _) => style,
+            (None, Some(style)) => style.parse().map_err(|err| self.config_error(err))?,
+            (None, None) => TagStyle::default(),
+        };
+        options.rust.tag_key = self.tag_key.clone().or_else(|| config.tag_key.clone());
+        options.rust.non_exhaustive = self.non_exhaustive || config.non_exhaustive;
+        options.rust.default = self.default || config.default;
+        options.rust.module = self.module.clone().or_else(|| config.module.clone());
+        options.rust.decode_base64 = self.decode_base64 || config.decode_base64;
+        options.rust.coerce_numeric_strings =
+            self.coerce_numeric_strings || config.coerce_numeric_strings;
+        options.rust.flat = self.flat || config.flat;
+        options.rust.trace_optional = self.trace_optional;
+        options.rust.shared = match (self.shared, &config.shared) {
+            (Some(pointer), _) => Some(pointer),
+            (None, Some(pointer)) => Some(pointer.parse().map_err(|err| self.config_error(err))?),
+            (None, None) => None,
+        };
+        if let Some(overrides) = &config.type_override {
+            options
+                .rust
+                .set_type_overrides(overrides)
+                .map_err(|err| self.config_error(err))?;
         }
+        if let Some(overrides) = &self.type_override {
+            // Looked up first to last, so these win over the config file's.
+            let from_config = std::mem::take(&mut options.rust.type_overrides);
+            options
+                .rust
+                .set_type_overrides(overrides)
+                .map_err(|err| Error::Usage(format!("error: {}", err)))?;
+            options.rust.type_overrides.extend(from_config);
+        }
+        if self.newtypes || config.newtypes {
+            options.rust.newtype_suffixes = self
+                .newtype_suffixes
+                .as_deref()
+                .or(config.newtype_suffixes.as_deref())
+                .unwrap_or("_id,Id")
+                .split(',')
+                .map(|suffix| suffix.trim().to_string())
+                .filter(|suffix| !suffix.is_empty())
+                .collect();
+        }
+        options.rust.null_policy = match (self.null_policy, &config.null_policy) {
+            (Some(policy), _) => policy,
+            (None, Some(policy)) => policy.parse().map_err(|err| self.config_error(err))?,
+            (None, None) => NullPolicy::default(),
+        };
+        options.rust.try_from = self.tryfrom || config.tryfrom;
+        options.rust.emit_tests = self.emit_tests || config.emit_tests;
+        options.rust.imports = self.with_imports || config.with_imports;
+        options.rust.decimal = self.decimal || config.decimal;
+        options.rust.infer_enums = (self.infer_enums || config.infer_enums).then_some(
+            self.max_enum_variants
+                .or(config.max_enum_variants)
+                .unwrap_or(5),
+        );
+        Ok(options)
+    }
+}
+
+/// Config file read from the current directory when `--config` isn't given.
+const CONFIG_FILE: &str = "json-to-struct.toml";
+
+/// Reads the config file at `path`, or `json-to-struct.toml` if it exists. With
+/// neither, every default comes from the command line.
+fn load_config(path: Option<&str>) -> Result<Config, Error> {
+    let path = match path {
+        Some(path) => path,
+        None if Path::new(CONFIG_FILE).exists() => CONFIG_FILE,
+        None => return Ok(Config::default()),
     };
+    let content = fs::read_to_string(path).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => Error::FileNotFound(path.to_string()),
+        _ => Error::Read(path.to_string(), err),
+    })?;
+    toml::from_str(&content).map_err(|err| Error::Config(path.to_string(), err.to_string()))
+}
 
-    println!("{}", output);
+/// Parses the command line, printing `--help` and `--version` output directly.
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, Error> {
+    Args::from_iter_safe(args).map_err(|err| match err.kind {
+        ErrorKind::HelpDisplayed | ErrorKind::VersionDisplayed => {
+            let _ = print_output(&err.message);
+            process::exit(0);
+        }
+        _ => Error::Usage(err.message),
+    })
 }
 
-fn rust_value_type(value: &Value) -> String {
-    match value {
-        Value::String(_) => "String".to_string(),
-        Value::Number(n) if n.is_i64() => "i64".to_string(),
-        Value::Number(n) if n.is_u64() => "u64".to_string(),
-        Value::Number(_) => "f64".to_string(),
-        Value::Bool(_) => "bool".to_string(),
-        Value::Array(_) => "Vec<Value>".to_string(),
-        Value::Object(_) => "HashMap<String, Value>".to_string(),
-        Value::Null => "Value".to_string(),
-    }
-}
-
-fn print_rust_struct(values: &[Value]) -> String {
-    let mut output = String::new();
-    let mut fields: BTreeMap<String, (bool, String)> = BTreeMap::new();
-
-    for value in values {
-        if let Value::Object(map) = value {
-            for (key, value) in map {
-                fields
-                    .entry(key.clone())
-                    .and_modify(|(is_optional, ty)| {
-                        *is_optional = *is_optional && value.is_null();
-                        if value.is_null() {
-                            return;
-                        }
-                        let new_ty = rust_value_type(value);
-                        if ty != &new_ty && !value.is_null() {
-                            *ty = new_ty;
-                        }
-                    })
-                    .or_insert((true, rust_value_type(value)));
+fn run() -> Result<(), Error> {
+    let args = parse_args(env::args())?;
+    log::set_level(match (args.quiet, args.verbose) {
+        (true, _) => log::Level::Quiet,
+        (_, true) => log::Level::Verbose,
+        _ => log::Level::Normal,
+    });
+    let config = load_config(args.config_path.as_deref())?;
+    let options = args.options(&config)?;
+    let (file_paths, language) = args.split_inputs(&config)?;
+
+    if args.interactive && file_paths.iter().any(|path| path == "-") {
+        return Err(Error::Usage(
+            "error: --interactive reads answers from stdin, so the input can't come from it."
+                .to_string(),
+        ));
+    }
+    if args.diff {
+        return print_diff(&args, file_paths, &options);
+    }
+    if !args.watch {
+        return generate_output(&args, file_paths, language, &options);
+    }
+    if file_paths.iter().any(|path| path == "-") {
+        return Err(Error::Usage(
+            "error: --watch needs input files, it can't read from stdin.".to_string(),
+        ));
+    }
+    let regenerate = || {
+        let time = Local::now().format("%H:%M:%S");
+        match generate_output(&args, file_paths, language, &options) {
+            Ok(()) => log::note(&format!(
+                "[{}] regenerated {}",
+                time,
+                args.out_path.as_deref().unwrap_or("output")
+            )),
+            Err(err) => eprintln!("[{}] error: {}", time, err),
+        }
+    };
+    regenerate();
+    watch::watch(file_paths, regenerate).map_err(Error::Watch)
+}
+
+/// Reads the input files, merging the objects from all of them in order. When
+/// `inexact` is given, the numbers in JSON input that an `f64` can't hold exactly are
+/// collected into it.
+fn read_values(
+    args: &Args,
+    file_paths: &[String],
+    mut inexact: Option<&mut Vec<String>>,
+) -> Result<Vec<Value>, Error> {
+    let mut json_array = Vec::new();
+    // With `--stream`, every object is folded into a running merge instead.
+    let mut accumulator = stream::Accumulator::default();
+    for file_path in file_paths {
+        let format = match args.input_format {
+            Some(format) => format,
+            None if args.ndjson => InputFormat::Ndjson,
+            None => InputFormat::from_path(file_path),
+        };
+        if args.stream && matches!(format, InputFormat::Json | InputFormat::Ndjson) {
+            let reader = open_input(file_path, args.gzip)?;
+            let limit = args
+                .sample
+                .map(|sample| sample.saturating_sub(accumulator.count()));
+            let before = accumulator.count();
+            match format {
+                InputFormat::Json => stream::stream_json(reader, limit, &mut accumulator),
+                _ => stream::stream_ndjson(reader, limit, &mut accumulator),
+            }
+            .map_err(Error::Parse)?;
+            log::verbose(&format!(
+                "streamed {} values from {}",
+                accumulator.count() - before,
+                file_path
+            ));
+            continue;
+        }
+        let content = read_input(file_path, args.gzip)?;
+        let documents = match format {
+            InputFormat::Json => vec![serde_json::from_str(&content).map_err(Error::Parse)?],
+            InputFormat::Ndjson => parse_ndjson(&content)?,
+            InputFormat::Yaml => vec![serde_yaml::from_str(&content).map_err(Error::ParseYaml)?],
+            InputFormat::Toml => vec![toml::from_str(&content).map_err(Error::ParseToml)?],
+        };
+        // The check parses the text again, so it is skipped when its warnings wouldn't
+        // be shown. Streamed input is never held as text, so it isn't checked either.
+        if log::enabled(log::Level::Normal) && !args.stream {
+            warn_duplicate_keys(file_path, &content, format);
+        }
+        if let Some(inexact) = inexact.as_mut() {
+            if matches!(format, InputFormat::Json | InputFormat::Ndjson) {
+                inexact.extend(inexact_numbers(&content));
+            }
+        }
+        log::verbose(&format!(
+            "read {} {} from {} as {}",
+            documents.len(),
+            if documents.len() == 1 {
+                "document"
+            } else {
+                "documents"
+            },
+            file_path,
+            format
+        ));
+        for document in documents {
+            let document = match &args.root_path {
+                Some(path) => select_path(document, path)?,
+                None => document,
+            };
+            let objects = match format {
+                // Every line of NDJSON is already a single value.
+                InputFormat::Ndjson => vec![document],
+                _ => into_objects(document)?,
+            };
+            if args.stream {
+                let limit = args.sample.unwrap_or(usize::MAX);
+                let objects = objects
+                    .into_iter()
+                    .take(limit.saturating_sub(accumulator.count()));
+                objects.for_each(|object| accumulator.fold(object));
+            } else {
+                json_array.extend(objects);
             }
         }
     }
+    if args.stream {
+        return Ok(accumulator.into_values());
+    }
+    Ok(json_array)
+}
 
-    for (key, (is_optional, value)) in &fields {
-        output.push_str(&format!("    {}: ", key));
-        output.push_str(if *is_optional { "Option<" } else { "" });
-        output.push_str(value);
-        output.push_str(if *is_optional { ">" } else { "" });
-        output.push_str(",\n");
-    }
-    output
-}
-
-fn print_typescript_interface(values: &[Value]) -> String {
-    let mut output = String::new();
-    let mut fields: BTreeMap<String, (bool, String)> = BTreeMap::new();
-
-    for value in values {
-        if let Value::Object(map) = value {
-            for (key, value) in map {
-                fields
-                    .entry(key.clone())
-                    .and_modify(|(is_optional, ty)| {
-                        *is_optional = *is_optional && value.is_null();
-                        if value.is_null() {
-                            return;
-                        }
-                        let new_ty = typescript_value_type(value);
-                        if ty != &new_ty && !value.is_null() {
-                            *ty = new_ty.to_owned();
-                        }
-                    })
-                    .or_insert((true, typescript_value_type(value).to_owned()));
-            }
+/// Prints how the schema inferred from the second input differs from the first's.
+fn print_diff(args: &Args, file_paths: &[String], options: &Options) -> Result<(), Error> {
+    let (old, new) = match file_paths {
+        [old, new] => (old, new),
+        _ => {
+            return Err(Error::Usage(
+                "error: --diff compares exactly two inputs, the old one first.".to_string(),
+            ))
         }
+    };
+    let old = infer_schema_with(
+        &read_values(args, std::slice::from_ref(old), None)?,
+        options,
+    );
+    let new = infer_schema_with(
+        &read_values(args, std::slice::from_ref(new), None)?,
+        options,
+    );
+    let changes = diff_schemas(&old, &new);
+    if changes.is_empty() {
+        log::note("the inferred schemas are the same");
+        return Ok(());
     }
+    print_output(&changes.join("\n"))
+}
 
-    for (key, (is_optional, ty)) in &fields {
-        output.push_str(&format!(
-            "    {}{}: ",
-            key,
-            if *is_optional { "?" } else { "" }
+/// Reads and merges the input files, then writes the generated code to `--out` or
+/// stdout.
+fn generate_output(
+    args: &Args,
+    file_paths: &[String],
+    language: Language,
+    options: &Options,
+) -> Result<(), Error> {
+    // Only Rust output is checked for numbers that lose precision.
+    let mut inexact = Vec::new();
+    let check_precision = language == Language::Rust && log::enabled(log::Level::Normal);
+    let mut json_array = read_values(args, file_paths, check_precision.then_some(&mut inexact))?;
+    if json_array.is_empty() && !args.allow_empty {
+        return Err(Error::EmptyInput);
+    }
+    // Streamed input is sampled as it is read, and merged into fewer values.
+    let sample = args.sample.filter(|_| !args.stream);
+    if let Some(sample) = sample.filter(|sample| *sample < json_array.len()) {
+        log::verbose(&format!(
+            "sampling the first {} of {} objects",
+            sample,
+            json_array.len()
         ));
-        output.push_str(ty);
-        output.push_str(";\n");
+        json_array.truncate(sample);
+    }
+    if log::enabled(log::Level::Verbose) {
+        if let InferredType::Object(fields) = infer_schema_with(&json_array, options) {
+            log::verbose(&format!(
+                "merged {} fields from {} objects",
+                fields.len(),
+                json_array.len()
+            ));
+            for field in fields {
+                let missing = if field.required {
+                    ""
+                } else {
+                    ", missing from some objects"
+                };
+                log::verbose(&format!("  {}: {}{}", field.name, field.ty, missing));
+            }
+        }
+    }
+    if args.stats {
+        eprintln!("{}", print_stats(&json_array, options));
+    }
+    let resolved;
+    let options = if args.interactive && language == Language::Rust {
+        let ambiguities = find_ambiguities(&json_array, options);
+        let resolutions =
+            interactive::resolve(&ambiguities, &mut io::stdin().lock(), &mut io::stderr())
+                .map_err(|err| Error::Read("stdin".to_string(), err))?;
+        let mut options = options.clone();
+        options.rust.resolutions.extend(resolutions);
+        resolved = options;
+        &resolved
+    } else {
+        if args.interactive {
+            log::warning("--interactive only applies to Rust output");
+        }
+        options
+    };
+    if check_precision {
+        for warning in precision_warnings(&json_array, &inexact, options) {
+            log::warning(&warning);
+        }
+    }
+    let output = if args.emit_ir.is_some() {
+        serde_json::to_string_pretty(&infer_schema_with(&json_array, options).to_json())
+            .expect("JSON values always serialize")
+    } else if args.collections {
+        generate_collections(&json_array, language, options)
+    } else {
+        generate(&json_array, language, options)
+    };
+    match &args.out_path {
+        Some(path) => write_output(path, &output),
+        None if args.check_path.is_some() => {
+            let path = args.check_path.as_deref().expect("checked above");
+            check_output(path, &output, args.check_exact)?;
+            log::note(&format!("{} is up to date", path));
+            Ok(())
+        }
+        None if args.clipboard => {
+            match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(&output)) {
+                Ok(()) => log::note("copied the output to the clipboard"),
+                Err(err) => {
+                    log::warning(&format!(
+                        "could not use the clipboard, printing instead: {}",
+                        err
+                    ));
+                    return print_output(&output);
+                }
+            }
+            Ok(())
+        }
+        None => print_output(&output),
+    }
+}
+
+/// The document formats inputs can be written in. They are all read into JSON values,
+/// so the generated code is the same whichever one the sample data comes in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputFormat {
+    Json,
+    Ndjson,
+    Yaml,
+    Toml,
+}
+
+impl InputFormat {
+    /// Picks the format from the file extension, ignoring a trailing `.gz`. Anything
+    /// unrecognised, including stdin, is read as JSON.
+    fn from_path(path: &str) -> InputFormat {
+        let name = path.trim_end_matches(".gz");
+        match Path::new(name).extension().and_then(|ext| ext.to_str()) {
+            Some("jsonl" | "ndjson") => InputFormat::Ndjson,
+            Some("yaml" | "yml") => InputFormat::Yaml,
+            Some("toml") => InputFormat::Toml,
+            _ => InputFormat::Json,
+        }
+    }
+}
+
+impl FromStr for InputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(InputFormat::Json),
+            "ndjson" => Ok(InputFormat::Ndjson),
+            "yaml" => Ok(InputFormat::Yaml),
+            "toml" => Ok(InputFormat::Toml),
+            _ => Err(format!(
+                "Invalid input format '{}'. Please use 'json', 'ndjson', 'yaml' or 'toml'.",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for InputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            InputFormat::Json => "json",
+            InputFormat::Ndjson => "ndjson",
+            InputFormat::Yaml => "yaml",
+            InputFormat::Toml => "toml",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Unwraps a parsed document holding either an array of objects or a single object,
+/// which is treated like an array holding just that object.
+fn into_objects(value: Value) -> Result<Vec<Value>, Error> {
+    match value {
+        Value::Array(values) => Ok(values),
+        value @ Value::Object(_) => Ok(vec![value]),
+        _ => Err(Error::InvalidInput(
+            "the input JSON should contain an object or an array of objects.".to_string(),
+        )),
     }
-    output
 }
 
-fn typescript_value_type(value: &Value) -> &'static str {
+/// Navigates into `value` along a dotted path such as `data.items`, where a numeric
+/// segment indexes into an array. The path has to lead to an array or an object.
+fn select_path(mut value: Value, path: &str) -> Result<Value, Error> {
+    let mut walked = Vec::new();
+    for segment in path.split('.') {
+        let next = match &mut value {
+            Value::Object(map) => map.remove(segment),
+            Value::Array(items) => segment
+                .parse::<usize>()
+                .ok()
+                .filter(|index| *index < items.len())
+                .map(|index| items.swap_remove(index)),
+            _ => None,
+        };
+        walked.push(segment);
+        value = next.ok_or_else(|| {
+            Error::InvalidInput(format!(
+                "--root-path {}: nothing found at {}",
+                path,
+                walked.join(".")
+            ))
+        })?;
+    }
     match value {
-        Value::Null => "null",
-        Value::Bool(_) => "boolean",
-        Value::Number(_) => "number",
-        Value::String(_) => "string",
-        Value::Array(_) => "Array",
-        Value::Object(_) => "Record<string, unknown>",
+        Value::Array(_) | Value::Object(_) => Ok(value),
+        _ => Err(Error::InvalidInput(format!(
+            "--root-path {}: expected an array or an object, found {}",
+            path, value
+        ))),
+    }
+}
+
+/// Parses a `--sample` count, which has to leave at least one object to infer from.
+fn parse_sample(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!(
+            "Invalid sample size '{}'. Use a number of objects, at least 1.",
+            s
+        )),
     }
 }
 
+/// Parses newline-delimited JSON, one value per line. Blank lines are skipped.
+fn parse_ndjson(content: &str) -> Result<Vec<Value>, Error> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|err| Error::ParseLine(index + 1, err))
+        })
+        .collect()
+}
+
+/// Most duplicate keys warned about for a single input file.
+const MAX_DUPLICATE_KEY_WARNINGS: usize = 5;
+
+/// Warns about keys repeated within an object in a JSON or NDJSON input, since only
+/// their last value is read. The YAML and TOML parsers reject duplicates themselves.
+fn warn_duplicate_keys(file_path: &str, content: &str, format: InputFormat) {
+    let duplicates: Vec<String> = match format {
+        InputFormat::Json => duplicates::duplicate_keys(content)
+            .into_iter()
+            .map(|path| format!("`{}`", path))
+            .collect(),
+        InputFormat::Ndjson => content
+            .lines()
+            .enumerate()
+            .flat_map(|(index, line)| {
+                duplicates::duplicate_keys(line)
+                    .into_iter()
+                    .map(move |path| format!("`{}` on line {}", path, index + 1))
+            })
+            .collect(),
+        InputFormat::Yaml | InputFormat::Toml => return,
+    };
+    for duplicate in duplicates.iter().take(MAX_DUPLICATE_KEY_WARNINGS) {
+        log::warning(&format!(
+            "{}: duplicate key {}; only its last value is used",
+            file_path, duplicate
+        ));
+    }
+    if duplicates.len() > MAX_DUPLICATE_KEY_WARNINGS {
+        log::warning(&format!(
+            "{}: {} more duplicate keys",
+            file_path,
+            duplicates.len() - MAX_DUPLICATE_KEY_WARNINGS
+        ));
+    }
+}
+
+/// Writes the generated code to `path`, creating any missing parent directories.
+fn write_output(path: &str, output: &str) -> Result<(), Error> {
+    let path = Path::new(path);
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(|err| Error::Write(path.display().to_string(), err))?;
+    }
+    fs::write(path, format!("{}\n", output))
+        .map_err(|err| Error::Write(path.display().to_string(), err))
+}
+
+/// Prints `output` to stdout. A pipe closed early, e.g. by `| head`, ends the output
+/// quietly rather than panicking as `println!` would.
+fn print_output(output: &str) -> Result<(), Error> {
+    match writeln!(io::stdout().lock(), "{}", output) {
+        Err(err) if err.kind() != io::ErrorKind::BrokenPipe => {
+            Err(Error::Write("stdout".to_string(), err))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Compares the file at `path` with `output` as `write_output` would write it,
+/// ignoring differences in whitespace unless `exact` is set.
+fn check_output(path: &str, output: &str, exact: bool) -> Result<(), Error> {
+    let existing = read_input(path, false)?;
+    let matches = if exact {
+        existing == format!("{}\n", output)
+    } else {
+        existing.split_whitespace().eq(output.split_whitespace())
+    };
+    if matches {
+        Ok(())
+    } else {
+        Err(Error::OutOfDate(path.to_string()))
+    }
+}
+
+/// Byte order mark some editors write at the start of UTF-8 files.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Maps an error reading `file_path` to the matching `Error`.
+fn read_error(file_path: &str, err: io::Error) -> Error {
+    match err.kind() {
+        io::ErrorKind::NotFound => Error::FileNotFound(file_path.to_string()),
+        _ => Error::Read(file_path.to_string(), err),
+    }
+}
+
+/// Opens an input, treating `-` as standard input. Gzip-compressed input is
+/// decompressed when `gzip` is set or the path ends in `.gz`, and a leading UTF-8
+/// byte order mark is skipped.
+fn open_input(file_path: &str, gzip: bool) -> Result<Box<dyn BufRead>, Error> {
+    let read_error = |err| read_error(file_path, err);
+    let reader: Box<dyn Read> = if file_path == "-" {
+        Box::new(io::stdin())
+    } else {
+        Box::new(fs::File::open(file_path).map_err(read_error)?)
+    };
+    let reader = if gzip || file_path.ends_with(".gz") {
+        Box::new(GzDecoder::new(reader))
+    } else {
+        reader
+    };
+
+    let mut reader = BufReader::new(reader);
+    // Files exported from Windows tools often start with a UTF-8 byte order mark.
+    if reader.fill_buf().map_err(read_error)?.starts_with(UTF8_BOM) {
+        reader.consume(UTF8_BOM.len());
+    }
+    Ok(Box::new(reader))
+}
+
+/// Reads the whole input document, as `open_input` opens it.
+fn read_input(file_path: &str, gzip: bool) -> Result<String, Error> {
+    let mut bytes = Vec::new();
+    open_input(file_path, gzip)?
+        .read_to_end(&mut bytes)
+        .map_err(|err| read_error(file_path, err))?;
+    String::from_utf8(bytes).map_err(|_| Error::Encoding(file_path.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde_json::json;
-
-    fn format_output(s: &str) -> String {
-        s.lines()
-            .map(|line| {
-                let line = line.trim_start();
-                if line.ends_with(',') {
-                    let len = line.len();
-                    format!("{};", &line[0..len - 1])
-                } else {
-                    line.to_string()
-                }
-            })
-            .collect::<Vec<String>>()
-            .join("\n")
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    #[test]
+    fn test_read_input_missing_file() {
+        let err = read_input("does/not/exist.json", false).unwrap_err();
+        assert!(matches!(err, Error::FileNotFound(_)));
+        assert_eq!(err.exit_code(), 1);
     }
 
     #[test]
-    fn test_print_rust_struct() {
-        let json_value = json!([
-            {
-                "name": "Alice",
-                "age": 30.0,
-                "is_student": false
-            },
-            {
-                "name": "Bob",
-                "age": 25.0,
-                "is_student": true,
-                "address": null
-            }
-        ]);
+    fn test_read_input_gzip() {
+        let path = env::temp_dir().join(format!("json-to-struct-{}.json.gz", process::id()));
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"{\"a\": 1}").unwrap();
+        fs::write(&path, encoder.finish().unwrap()).unwrap();
 
-        let expected_output =
-            "address: Option<Value>;\nage: f64;\nis_student: bool;\nname: String;";
+        assert_eq!(
+            read_input(path.to_str().unwrap(), false).unwrap(),
+            "{\"a\": 1}"
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_input_strips_bom() {
+        let path = env::temp_dir().join(format!("json-to-struct-{}-bom.json", process::id()));
+        fs::write(&path, b"\xEF\xBB\xBF{\"a\": 1}").unwrap();
+        assert_eq!(
+            read_input(path.to_str().unwrap(), false).unwrap(),
+            "{\"a\": 1}"
+        );
 
-        let actual_output = format_output(&print_rust_struct(&json_value.as_array().unwrap()));
-        assert_eq!(actual_output, expected_output);
+        fs::write(&path, b"{\"a\": \"\xFF\"}").unwrap();
+        let err = read_input(path.to_str().unwrap(), false).unwrap_err();
+        assert!(matches!(err, Error::Encoding(_)));
+        fs::remove_file(path).unwrap();
     }
 
     #[test]
-    fn test_print_rust_struct_partial_fields() {
-        let json_value = json!([
-            {
-                "name": "Alice",
-                "age": 30.0
-            },
-            {
-                "name": "Bob",
-                "is_student": true
-            }
-        ]);
+    fn test_write_output_creates_parent_directories() {
+        let dir = env::temp_dir().join(format!("json-to-struct-{}", process::id()));
+        let path = dir.join("models").join("data.rs");
+
+        write_output(path.to_str().unwrap(), "struct Data {\n}").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "struct Data {\n}\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
 
-        let expected_output = "age: Option<f64>;\nis_student: Option<bool>;\nname: String;";
+    #[test]
+    fn test_check_output() {
+        let path = env::temp_dir().join(format!("json-to-struct-check-{}.rs", process::id()));
+        let path_str = path.to_str().unwrap();
+        fs::write(&path, "struct Data {\n  name: String,\n}\n").unwrap();
 
-        let actual_output = format_output(&print_rust_struct(&json_value.as_array().unwrap()));
-        assert_eq!(actual_output, expected_output);
+        assert!(check_output(path_str, "struct Data {\n    name: String,\n}", false).is_ok());
+        assert!(matches!(
+            check_output(path_str, "struct Data {\n    name: String,\n}", true),
+            Err(Error::OutOfDate(_))
+        ));
+        assert!(check_output(path_str, "struct Data {\n  name: String,\n}", true).is_ok());
+        assert!(matches!(
+            check_output(path_str, "struct Data {\n    age: i64,\n}", false),
+            Err(Error::OutOfDate(_))
+        ));
+        fs::remove_file(path).unwrap();
     }
 
     #[test]
-    fn test_print_typescript_interface() {
-        let json_value = json!([
-            {
-                "name": "Alice",
-                "age": 30.0,
-                "is_student": false
-            },
-            {
-                "name": "Bob",
-                "age": 25.0,
-                "is_student": true
-            }
-        ]);
+    fn test_empty_input() {
+        let dir = env::temp_dir().join(format!("json-to-struct-empty-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("empty.json");
+        let out = dir.join("data.rs");
+        fs::write(&input, "[]").unwrap();
+        let run = |extra: &[&str]| {
+            let mut args = vec!["json-to-struct", input.to_str().unwrap(), "rust"];
+            args.extend(extra);
+            let args = parse_args(args.iter().map(|arg| arg.to_string())).unwrap();
+            let options = args.options(&Config::default()).unwrap();
+            generate_output(&args, &args.inputs[..1], Language::Rust, &options)
+        };
 
-        let expected_output = "age: number;\nis_student: boolean;\nname: string;";
+        let err = run(&[]).unwrap_err();
+        assert!(matches!(err, Error::EmptyInput));
+        assert_eq!(err.exit_code(), 3);
+        run(&["--allow-empty", "--out", out.to_str().unwrap()]).unwrap();
+        assert_eq!(fs::read_to_string(&out).unwrap(), "struct Data {\n}\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_merge_input_files() {
+        let dir = env::temp_dir().join(format!("json-to-struct-merge-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let first = dir.join("first.json");
+        let second = dir.join("second.ndjson");
+        let out = dir.join("data.rs");
+        fs::write(
+            &first,
+            r#"[{"id": 1, "name": "a"}, {"id": 2, "name": "b"}]"#,
+        )
+        .unwrap();
+        fs::write(&second, "{\"id\": 3, \"email\": \"c@d.e\"}\n").unwrap();
 
-        let actual_output =
-            format_output(&print_typescript_interface(&json_value.as_array().unwrap()));
-        assert_eq!(actual_output, expected_output);
+        let args = [
+            "json-to-struct",
+            first.to_str().unwrap(),
+            second.to_str().unwrap(),
+            "rust",
+            "--out",
+            out.to_str().unwrap(),
+        ];
+        let args = parse_args(args.iter().map(|arg| arg.to_string())).unwrap();
+        let options = args.options(&Config::default()).unwrap();
+        let (file_paths, language) = args.split_inputs(&Config::default()).unwrap();
+        generate_output(&args, file_paths, language, &options).unwrap();
+
+        // `name` is missing from the second file and `email` from the first.
+        assert_eq!(
+            fs::read_to_string(&out).unwrap(),
+            "struct Data {\n    email: Option<String>,\n    id: i64,\n    name: Option<String>,\n}\n"
+        );
+        fs::remove_dir_all(dir).unwrap();
     }
 
     #[test]
-    fn test_print_typescript_interface_partial_fields() {
-        let json_value = json!([
-            {
-                "name": "Alice",
-            },
-            {
-                "name": "Bob",
-                "is_student": true,
-            }
-        ]);
+    fn test_sample() {
+        let dir = env::temp_dir().join(format!("json-to-struct-sample-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("data.json");
+        let out = dir.join("data.rs");
+        fs::write(&input, r#"[{"a": 1, "b": 1}, {"a": 2, "b": 2}, {"a": 3}]"#).unwrap();
+        let run = |extra: &[&str]| {
+            let mut args = vec!["json-to-struct", input.to_str().unwrap(), "rust", "--out"];
+            args.push(out.to_str().unwrap());
+            args.extend(extra);
+            let args = parse_args(args.iter().map(|arg| arg.to_string()))?;
+            let options = args.options(&Config::default())?;
+            generate_output(&args, &args.inputs[..1], Language::Rust, &options)?;
+            Ok::<_, Error>(fs::read_to_string(&out).unwrap())
+        };
 
-        let expected_output = "is_student?: boolean;\nname: string;";
+        // `b` is only missing from the object left out of the sample.
+        assert_eq!(
+            run(&["--sample", "2"]).unwrap(),
+            "struct Data {\n    a: i64,\n    b: i64,\n}\n"
+        );
+        assert_eq!(
+            run(&["--sample", "2", "--stream"]).unwrap(),
+            "struct Data {\n    a: i64,\n    b: i64,\n}\n"
+        );
+        assert_eq!(
+            run(&[]).unwrap(),
+            "struct Data {\n    a: i64,\n    b: Option<i64>,\n}\n"
+        );
+        assert!(matches!(run(&["--sample", "0"]), Err(Error::Usage(_))));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = [
+            "json-to-struct",
+            "a.json",
+            "b.json",
+            "rust",
+            "--pub",
+            "--indent",
+            "2",
+        ];
+        let args = parse_args(args.iter().map(|arg| arg.to_string())).unwrap();
+        let (file_paths, language) = args.split_inputs(&Config::default()).unwrap();
+        assert_eq!(file_paths, ["a.json", "b.json"]);
+        assert_eq!(language, Language::Rust);
+        let options = args.options(&Config::default()).unwrap();
+        assert!(options.rust.public);
+        assert_eq!(options.indent.as_deref(), Some("  "));
+
+        let args = ["json-to-struct", "data.json", "rust", "--bogus"];
+        let err = parse_args(args.iter().map(|arg| arg.to_string())).unwrap_err();
+        assert!(matches!(&err, Error::Usage(message) if message.contains("--bogus")));
+        assert_eq!(err.exit_code(), 2);
+    }
+
+    #[test]
+    fn test_format_from_out_extension() {
+        let split = |args: &[&str]| {
+            let args = parse_args(args.iter().map(|arg| arg.to_string())).unwrap();
+            let (files, language) = args.split_inputs(&Config::default()).unwrap();
+            (files.to_vec(), language)
+        };
+
+        let (files, language) = split(&["json-to-struct", "a.json", "--out", "models.ts"]);
+        assert_eq!(files, ["a.json"]);
+        assert_eq!(language, Language::TypeScript);
+        let (files, language) = split(&["json-to-struct", "a.json", "go", "--out", "models.ts"]);
+        assert_eq!(files, ["a.json"]);
+        assert_eq!(language, Language::Go);
+    }
+
+    #[test]
+    fn test_args_with_config() {
+        let config = Config {
+            format: Some("go".to_string()),
+            name: Some("User".to_string()),
+            indent: Some(config::Indent::Text("tab".to_string())),
+            ..Config::default()
+        };
+
+        let args = ["json-to-struct", "data.json", "--indent", "2"];
+        let args = parse_args(args.iter().map(|arg| arg.to_string())).unwrap();
+        let (file_paths, language) = args.split_inputs(&config).unwrap();
+        assert_eq!(file_paths, ["data.json"]);
+        assert_eq!(language, Language::Go);
+        let options = args.options(&config).unwrap();
+        assert_eq!(options.name, "User");
+        assert_eq!(options.indent.as_deref(), Some("  "));
+
+        let config = Config {
+            keep_original_names: true,
+            all_optional: true,
+            infer_enums: true,
+            max_enum_variants: Some(3),
+            null_policy: Some("strict".to_string()),
+            ..Config::default()
+        };
+        let args = ["json-to-struct", "data.json", "rust", "--all-required"];
+        let args = parse_args(args.iter().map(|arg| arg.to_string())).unwrap();
+        let options = args.options(&config).unwrap();
+        assert!(options.rust.keep_names);
+        assert!(options.all_required && !options.all_optional);
+        assert_eq!(options.rust.infer_enums, Some(3));
+        assert_eq!(options.rust.null_policy, NullPolicy::Strict);
+
+        let args = ["json-to-struct", "data.json"];
+        let args = parse_args(args.iter().map(|arg| arg.to_string())).unwrap();
+        let err = args.split_inputs(&Config::default()).unwrap_err();
+        assert!(matches!(err, Error::Usage(_)));
+
+        let args = ["json-to-struct", "-f", "typescript", "a.json", "rust"];
+        let args = parse_args(args.iter().map(|arg| arg.to_string())).unwrap();
+        let (file_paths, language) = args.split_inputs(&config).unwrap();
+        assert_eq!(file_paths, ["a.json", "rust"]);
+        assert_eq!(language, Language::TypeScript);
+    }
+
+    #[test]
+    fn test_input_formats() {
+        assert_eq!(InputFormat::from_path("config.yml"), InputFormat::Yaml);
+        assert_eq!(InputFormat::from_path("Cargo.toml"), InputFormat::Toml);
+        assert_eq!(
+            InputFormat::from_path("events.jsonl.gz"),
+            InputFormat::Ndjson
+        );
+        assert_eq!(InputFormat::from_path("-"), InputFormat::Json);
+
+        let yaml: Value = serde_yaml::from_str("- name: Alice\n  age: 30\n").unwrap();
+        let values = into_objects(yaml).unwrap();
+        assert_eq!(values[0]["age"], Value::from(30));
+
+        let toml: Value =
+            toml::from_str("name = \"Alice\"\n[address]\ncity = \"Paris\"\n").unwrap();
+        let values = into_objects(toml).unwrap();
+        assert_eq!(values[0]["address"]["city"], Value::from("Paris"));
+        assert_eq!(InputFormat::Ndjson.to_string(), "ndjson");
+    }
+
+    #[test]
+    fn test_select_path() {
+        let document: Value =
+            serde_json::from_str(r#"{"data": {"items": [{"a": 1}, {"b": [{"c": 2}]}]}}"#).unwrap();
+        let items = select_path(document.clone(), "data.items").unwrap();
+        assert_eq!(into_objects(items).unwrap().len(), 2);
+        assert_eq!(
+            select_path(document.clone(), "data.items.1.b").unwrap()[0]["c"],
+            Value::from(2)
+        );
+
+        let err = select_path(document.clone(), "data.missing.items").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "--root-path data.missing.items: nothing found at data.missing"
+        );
+        assert!(select_path(document, "data.items.0.a").is_err());
+    }
+
+    #[test]
+    fn test_parse_ndjson() {
+        let values = parse_ndjson("{\"a\": 1}\n\n  \n{\"a\": 2, \"b\": true}\n").unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[1]["b"], Value::Bool(true));
 
-        let actual_output =
-            format_output(&print_typescript_interface(&json_value.as_array().unwrap()));
-        assert_eq!(actual_output, expected_output);
+        let err = parse_ndjson("{\"a\": 1}\n{\"a\":\n").unwrap_err();
+        assert!(matches!(err, Error::ParseLine(2, _)));
     }
 }
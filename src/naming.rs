@@ -0,0 +1,263 @@
+//! Conversions from JSON keys to identifiers in the generated code.
+
+/// Converts a JSON key such as `shipping_address`, `user-profile` or `billing info`
+/// into a type name like `ShippingAddress`. Every run of characters that can't appear
+/// in an identifier starts a new word, and names that would start with a digit are
+/// prefixed with an underscore.
+pub(crate) fn pascal_case(key: &str) -> String {
+    let name: String = key
+        .split(|c: char| !c.is_alphanumeric())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    if name.is_empty() {
+        "Type".to_string()
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+/// Turns a plural English noun such as `users` or `categories` into its singular, for
+/// naming the type of a collection's elements. A small set of suffix rules covers the
+/// common cases; words that don't look plural, like `status`, are left alone.
+pub(crate) fn singularize(word: &str) -> String {
+    let lower = word.to_lowercase();
+    let strip = |suffix_len: usize, replacement: &str| {
+        format!("{}{}", &word[..word.len() - suffix_len], replacement)
+    };
+    if lower.ends_with("ies") && word.len() > 3 {
+        strip(3, "y")
+    } else if ["sses", "xes", "ches", "shes", "zzes"]
+        .iter()
+        .any(|suffix| lower.ends_with(suffix))
+    {
+        strip(2, "")
+    } else if lower.ends_with('s')
+        && !["ss", "us", "is"]
+            .iter()
+            .any(|suffix| lower.ends_with(suffix))
+        && word.len() > 1
+    {
+        strip(1, "")
+    } else {
+        word.to_string()
+    }
+}
+
+/// Converts a JSON key such as `firstName` or `created-at` into a snake_case Rust
+/// identifier. Keys that would start with a digit are prefixed with an underscore.
+pub(crate) fn snake_case(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    let mut output = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_alphanumeric() {
+            if c.is_uppercase() && i > 0 {
+                let prev = chars[i - 1];
+                let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+                if prev.is_lowercase()
+                    || prev.is_ascii_digit()
+                    || (prev.is_uppercase() && next_is_lower)
+                {
+                    output.push('_');
+                }
+            }
+            output.extend(c.to_lowercase());
+        } else if !output.ends_with('_') {
+            output.push('_');
+        }
+    }
+
+    let output = output.trim_matches('_');
+    if output.is_empty() {
+        "field".to_string()
+    } else if output.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", output)
+    } else {
+        output.to_string()
+    }
+}
+
+/// Converts a JSON key such as `first_name` or `created-at` into a lowerCamelCase
+/// identifier like `firstName`.
+pub(crate) fn camel_case(key: &str) -> String {
+    let mut output = String::new();
+    for (i, word) in snake_case(key)
+        .split('_')
+        .filter(|w| !w.is_empty())
+        .enumerate()
+    {
+        if i == 0 {
+            output.push_str(word);
+        } else {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                output.extend(first.to_uppercase().chain(chars));
+            }
+        }
+    }
+    if output.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", output)
+    } else {
+        output
+    }
+}
+
+/// Converts a JSON key such as `first_name` or `created-at` into an UpperCamelCase
+/// identifier like `FirstName`.
+pub(crate) fn upper_camel_case(key: &str) -> String {
+    let name = camel_case(key);
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => name,
+    }
+}
+
+/// Converts a string value such as `in-progress` into an enum variant name like
+/// `InProgress`.
+pub(crate) fn variant_name(value: &str) -> String {
+    let name = upper_camel_case(value);
+    if name == "Self" {
+        "Self_".to_string()
+    } else {
+        name
+    }
+}
+
+/// Keywords that cannot be used as plain Rust identifiers.
+const RUST_KEYWORDS: [&str; 52] = [
+    "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+    "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "async", "await", "dyn", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield", "try", "gen",
+];
+
+/// Turns a JSON key into a valid Rust field name. Keywords use the raw identifier
+/// form, except for the few that can't be raw, which get a trailing underscore.
+pub(crate) fn rust_field_name(key: &str) -> String {
+    let name = snake_case(key);
+    match name.as_str() {
+        "self" | "super" | "crate" => format!("{}_", name),
+        _ if RUST_KEYWORDS.contains(&name.as_str()) => format!("r#{}", name),
+        _ => name,
+    }
+}
+
+/// Keeps a JSON key as the Rust field name when it is already a valid identifier,
+/// e.g. `firstName`, applying the same keyword handling as [`rust_field_name`]. Other
+/// keys are converted with [`rust_field_name`].
+pub(crate) fn rust_original_field_name(key: &str) -> String {
+    let is_identifier = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && key != "_";
+    match key {
+        _ if !is_identifier => rust_field_name(key),
+        "self" | "super" | "crate" | "Self" => format!("{}_", key),
+        _ if RUST_KEYWORDS.contains(&key) => format!("r#{}", key),
+        _ => key.to_string(),
+    }
+}
+
+/// Appends a counter to the field names converted from `keys` until they are unique,
+/// since keys such as `firstName` and `first_name` convert to the same name. A field
+/// named after its key keeps the name, so only converted keys get a counter. The
+/// counter follows an underscore in snake_case names, like `user_id_2`, and is appended
+/// directly to camelCase and PascalCase ones, like `UserId2`.
+pub(crate) fn dedupe_field_names(keys: &[&str], names: &mut [String]) {
+    let (unchanged, renamed): (Vec<usize>, Vec<usize>) =
+        (0..names.len()).partition(|&i| names[i].trim_start_matches("r#") == keys[i]);
+    let mut taken: Vec<String> = Vec::new();
+    for i in unchanged.into_iter().chain(renamed) {
+        let base = names[i].trim_start_matches("r#").trim_end_matches('_');
+        let mut candidate = names[i].clone();
+        let mut counter = 2;
+        while taken.contains(&candidate) {
+            candidate = if names[i].contains(char::is_uppercase) {
+                format!("{}{}", base, counter)
+            } else {
+                format!("{}_{}", base, counter)
+            };
+            counter += 1;
+        }
+        names[i] = candidate.clone();
+        taken.push(candidate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("firstName"), "first_name");
+        assert_eq!(snake_case("created-at"), "created_at");
+        assert_eq!(snake_case("userID"), "user_id");
+        assert_eq!(snake_case("HTTPServer"), "http_server");
+        assert_eq!(snake_case("1st_place"), "_1st_place");
+        assert_eq!(snake_case("is_student"), "is_student");
+    }
+
+    #[test]
+    fn test_pascal_case() {
+        assert_eq!(pascal_case("shipping_address"), "ShippingAddress");
+        assert_eq!(pascal_case("user-profile"), "UserProfile");
+        assert_eq!(pascal_case("billing info"), "BillingInfo");
+        assert_eq!(pascal_case("lineItems"), "LineItems");
+        assert_eq!(pascal_case("3d-model"), "_3dModel");
+        assert_eq!(pascal_case("@@"), "Type");
+    }
+
+    #[test]
+    fn test_singularize() {
+        assert_eq!(singularize("users"), "user");
+        assert_eq!(singularize("Categories"), "Category");
+        assert_eq!(singularize("addresses"), "address");
+        assert_eq!(singularize("boxes"), "box");
+        assert_eq!(singularize("status"), "status");
+        assert_eq!(singularize("data"), "data");
+    }
+
+    #[test]
+    fn test_camel_case() {
+        assert_eq!(camel_case("first_name"), "firstName");
+        assert_eq!(camel_case("firstName"), "firstName");
+        assert_eq!(camel_case("created-at"), "createdAt");
+        assert_eq!(camel_case("1st_place"), "_1stPlace");
+    }
+
+    #[test]
+    fn test_variant_name() {
+        assert_eq!(variant_name("active"), "Active");
+        assert_eq!(variant_name("in-progress"), "InProgress");
+        assert_eq!(variant_name("ON_HOLD"), "OnHold");
+        assert_eq!(variant_name("2fa"), "_2fa");
+        assert_eq!(variant_name("Self"), "Self_");
+    }
+
+    #[test]
+    fn test_rust_original_field_name() {
+        assert_eq!(rust_original_field_name("firstName"), "firstName");
+        assert_eq!(rust_original_field_name("type"), "r#type");
+        assert_eq!(rust_original_field_name("created-at"), "created_at");
+        assert_eq!(rust_original_field_name("1st"), "_1st");
+    }
+
+    #[test]
+    fn test_rust_field_name() {
+        assert_eq!(rust_field_name("type"), "r#type");
+        assert_eq!(rust_field_name("match"), "r#match");
+        assert_eq!(rust_field_name("self"), "self_");
+        assert_eq!(rust_field_name("firstName"), "first_name");
+        assert_eq!(rust_field_name("name"), "name");
+    }
+}
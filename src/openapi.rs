@@ -0,0 +1,195 @@
+//! OpenAPI 3.0 `components.schemas` generation.
+
+use crate::naming::pascal_case;
+use crate::schema::{infer_type, InferredField, InferredType, ScalarType};
+use crate::Options;
+use serde_json::{json, Map, Value};
+
+/// Prefix of the references to the generated components.
+const COMPONENTS_REF: &str = "#/components/schemas/";
+
+/// Accumulates the schema components, in declaration order, while walking the schema.
+struct OpenApiGenerator<'a> {
+    options: &'a Options,
+    schemas: Vec<(String, Value)>,
+}
+
+impl<'a> OpenApiGenerator<'a> {
+    /// Picks a component name that hasn't been generated yet by appending a counter.
+    fn unique_schema_name(&self, name: &str) -> String {
+        let mut candidate = name.to_string();
+        let mut counter = 2;
+        while self
+            .schemas
+            .iter()
+            .any(|(existing, _)| existing == &candidate)
+        {
+            candidate = format!("{}{}", name, counter);
+            counter += 1;
+        }
+        candidate
+    }
+
+    /// Builds the schema for an inferred type. Objects become components of their own
+    /// and are referenced with `$ref`.
+    fn type_schema(&mut self, key: &str, ty: &InferredType) -> Value {
+        match ty {
+            InferredType::Unknown => json!({}),
+            InferredType::Scalar(scalar) => json!({ "type": openapi_scalar_type(*scalar) }),
+            InferredType::Object(fields) => {
+                let name = self.options.type_name(&pascal_case(key));
+                let name = self.generate_schema(&name, fields);
+                json!({ "$ref": format!("{}{}", COMPONENTS_REF, name) })
+            }
+            InferredType::Array(items) => {
+                let key = match **items {
+                    InferredType::Array(_) => key.to_string(),
+                    _ => self.options.element_key(key),
+                };
+                json!({ "type": "array", "items": self.type_schema(&key, items) })
+            }
+            // OpenAPI 3.0 has no `null` type, and ignores the siblings of a `$ref`.
+            InferredType::Optional(inner) => {
+                let schema = self.type_schema(key, inner);
+                let mut schema = match schema {
+                    Value::Object(map) if map.contains_key("$ref") => {
+                        json!({ "allOf": [map] })
+                    }
+                    schema => schema,
+                };
+                schema["nullable"] = json!(true);
+                schema
+            }
+            InferredType::Union(members) => {
+                let schemas: Vec<Value> = members
+                    .iter()
+                    .map(|member| self.type_schema(key, member))
+                    .collect();
+                json!({ "anyOf": schemas })
+            }
+        }
+    }
+
+    /// Generates a component for an inferred object, along with any nested components
+    /// it needs. Returns the name given to the component.
+    fn generate_schema(&mut self, name: &str, fields: &[InferredField]) -> String {
+        let name = self.unique_schema_name(name);
+        let index = self.schemas.len();
+        self.schemas.push((name.clone(), Value::Null));
+
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for field in fields {
+            if field.required {
+                required.push(json!(field.name));
+            }
+            properties.insert(field.name.clone(), self.type_schema(&field.name, &field.ty));
+        }
+        let mut schema = json!({ "type": "object", "properties": properties });
+        // An empty `required` list isn't valid in OpenAPI 3.0.
+        if !required.is_empty() {
+            schema["required"] = Value::Array(required);
+        }
+        self.schemas[index].1 = schema;
+        name
+    }
+}
+
+/// Prints an OpenAPI 3.0 `components.schemas` fragment, as YAML, with a component for
+/// the objects in `values` followed by one for every nested object. Properties that
+/// appear in every object are listed as `required`, and optional ones are `nullable`.
+pub fn print_openapi_components(values: &[Value], options: &Options) -> String {
+    let values: Vec<&Value> = values.iter().collect();
+    let mut generator = OpenApiGenerator {
+        options,
+        schemas: Vec::new(),
+    };
+    let fields = match infer_type(&values, options) {
+        InferredType::Object(fields) => fields,
+        _ => Vec::new(),
+    };
+    generator.generate_schema(&options.type_name(&options.name), &fields);
+
+    let schemas: Map<String, Value> = generator.schemas.into_iter().collect();
+    let document = json!({ "components": { "schemas": schemas } });
+    serde_yaml::to_string(&document)
+        .expect("serializing a Value can't fail")
+        .trim_end()
+        .to_string()
+}
+
+/// Maps a scalar to its OpenAPI type name.
+pub fn openapi_scalar_type(scalar: ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::Bool => "boolean",
+        ScalarType::Integer => "integer",
+        ScalarType::Float => "number",
+        ScalarType::String => "string",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_print_openapi_components() {
+        let json_value = json!([
+            { "name": "alice", "age": 30, "tags": ["a"], "address": { "city": "Paris" }, "id": 1 },
+            { "name": "bob", "age": null, "tags": [], "address": null, "id": "x", "pets": [{ "kind": "cat" }] }
+        ]);
+
+        let expected_output = "components:
+  schemas:
+    Data:
+      type: object
+      properties:
+        address:
+          allOf:
+          - $ref: '#/components/schemas/Address'
+          nullable: true
+        age:
+          type: integer
+          nullable: true
+        id:
+          anyOf:
+          - type: integer
+          - type: string
+        name:
+          type: string
+        pets:
+          type: array
+          items:
+            $ref: '#/components/schemas/Pet'
+        tags:
+          type: array
+          items:
+            type: string
+      required:
+      - address
+      - age
+      - id
+      - name
+      - tags
+    Address:
+      type: object
+      properties:
+        city:
+          type: string
+      required:
+      - city
+    Pet:
+      type: object
+      properties:
+        kind:
+          type: string
+      required:
+      - kind";
+
+        assert_eq!(
+            print_openapi_components(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+}
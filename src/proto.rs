@@ -0,0 +1,169 @@
+//! Protocol Buffers (proto3) message generation.
+
+use crate::fields::{array_elements, merge_fields};
+use crate::naming::{dedupe_field_names, pascal_case, snake_case};
+use crate::Options;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Well-known type used for values whose type can't be inferred.
+const PROTO_ANY_VALUE: &str = "google.protobuf.Value";
+
+/// Accumulates the generated messages, in declaration order, while walking the input.
+struct ProtoGenerator<'a> {
+    options: &'a Options,
+    indent: &'a str,
+    messages: Vec<(String, String)>,
+}
+
+impl<'a> ProtoGenerator<'a> {
+    /// Picks a message name that hasn't been generated yet by appending a counter.
+    fn unique_message_name(&self, name: &str) -> String {
+        let mut candidate = name.to_string();
+        let mut counter = 2;
+        while self
+            .messages
+            .iter()
+            .any(|(existing, _)| existing == &candidate)
+        {
+            candidate = format!("{}{}", name, counter);
+            counter += 1;
+        }
+        candidate
+    }
+
+    /// Maps the values of a single field or array to a type, generating a message
+    /// when they are all objects.
+    fn value_type(&mut self, key: &str, values: &[&Value]) -> String {
+        if !values.is_empty() && values.iter().all(|value| value.is_object()) {
+            return self.generate_message(&self.options.type_name(&pascal_case(key)), values);
+        }
+        let types: BTreeSet<&str> = values.iter().map(|value| proto_value_type(value)).collect();
+        match types.into_iter().collect::<Vec<&str>>().as_slice() {
+            [ty] => ty.to_string(),
+            ["double", "int64"] => "double".to_string(),
+            _ => PROTO_ANY_VALUE.to_string(),
+        }
+    }
+
+    /// Generates a message for the given objects along with any nested messages it
+    /// needs. Field numbers follow the order the fields are listed in. Returns the
+    /// name given to the message.
+    fn generate_message(&mut self, name: &str, values: &[&Value]) -> String {
+        let name = self.unique_message_name(name);
+        let index = self.messages.len();
+        self.messages.push((name.clone(), String::new()));
+
+        let mut output = String::new();
+        let fields = merge_fields(values, self.options);
+        let keys: Vec<&str> = fields.iter().map(|(key, _)| key.as_str()).collect();
+        let mut names: Vec<String> = keys.iter().map(|key| snake_case(key)).collect();
+        dedupe_field_names(&keys, &mut names);
+        for (number, ((key, field), field_name)) in fields.iter().zip(&names).enumerate() {
+            let present: Vec<&Value> = field
+                .values
+                .iter()
+                .filter(|v| !v.is_null())
+                .copied()
+                .collect();
+            let label_and_type = if !present.is_empty() && present.iter().all(|v| v.is_array()) {
+                let elements = array_elements(&present);
+                let ty = if elements.iter().any(|value| value.is_array()) {
+                    PROTO_ANY_VALUE.to_string()
+                } else {
+                    self.value_type(&self.options.element_key(key), &elements)
+                };
+                format!("repeated {}", ty)
+            } else {
+                let ty = self.value_type(key, &present);
+                if field.is_optional && ty != PROTO_ANY_VALUE {
+                    format!("optional {}", ty)
+                } else {
+                    ty
+                }
+            };
+            output.push_str(&format!(
+                "{}{} {} = {};\n",
+                self.indent,
+                label_and_type,
+                field_name,
+                number + 1
+            ));
+        }
+
+        self.messages[index].1 = format!("message {} {{\n{}}}", name, output);
+        name
+    }
+}
+
+/// Prints a proto3 file with the root message followed by a message for every nested
+/// object. `google/protobuf/struct.proto` is imported when a field falls back to
+/// `google.protobuf.Value`.
+pub fn print_proto_message(values: &[Value], options: &Options) -> String {
+    let values: Vec<&Value> = values.iter().collect();
+    let mut generator = ProtoGenerator {
+        options,
+        indent: options.indent.as_deref().unwrap_or("  "),
+        messages: Vec::new(),
+    };
+    generator.generate_message(&options.type_name(&options.name), &values);
+
+    let messages: Vec<String> = generator
+        .messages
+        .into_iter()
+        .map(|(_, body)| body)
+        .collect();
+    let mut output = "syntax = \"proto3\";\n\n".to_string();
+    if messages
+        .iter()
+        .any(|message| message.contains(PROTO_ANY_VALUE))
+    {
+        output.push_str("import \"google/protobuf/struct.proto\";\n\n");
+    }
+    output.push_str(&messages.join("\n\n"));
+    output
+}
+
+/// Maps a single JSON value to its proto3 scalar type.
+pub fn proto_value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Bool(_) => "bool",
+        Value::Number(n) if n.is_f64() => "double",
+        Value::Number(_) => "int64",
+        Value::String(_) => "string",
+        Value::Null | Value::Array(_) | Value::Object(_) => PROTO_ANY_VALUE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_print_proto_message() {
+        let json_value = json!([
+            { "userName": "alice", "age": 30, "tags": ["a"], "address": { "city": "Paris" } },
+            { "userName": "bob", "tags": [], "address": { "city": "Oslo" }, "meta": null }
+        ]);
+
+        let expected_output = "syntax = \"proto3\";\n\nimport \"google/protobuf/struct.proto\";\n\nmessage Data {\n  Address address = 1;\n  optional int64 age = 2;\n  google.protobuf.Value meta = 3;\n  repeated string tags = 4;\n  string user_name = 5;\n}\n\nmessage Address {\n  string city = 1;\n}";
+
+        assert_eq!(
+            print_proto_message(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_proto_message_colliding_keys() {
+        let json_value = json!([{ "userId": 1, "user_id": 2 }]);
+
+        let expected_output = "syntax = \"proto3\";\n\nmessage Data {\n  int64 user_id_2 = 1;\n  int64 user_id = 2;\n}";
+
+        assert_eq!(
+            print_proto_message(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Python dataclass generation.
+
+use crate::fields::{merge_fields, value_field_type};
+use crate::naming::{dedupe_field_names, snake_case};
+use crate::Options;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Keywords that can't name an attribute, along with `field`, which would shadow
+/// `dataclasses.field` in the class body. Such names get a trailing underscore.
+const PYTHON_KEYWORDS: [&str; 33] = [
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif",
+    "else", "except", "field", "finally", "for", "from", "global", "if", "import", "in", "is",
+    "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+/// Prints a `@dataclass` with type hints. Optional fields default to `None`, so they
+/// are listed after the required ones as dataclasses require. Keys are converted to
+/// snake_case attribute names, and an attribute whose name differs from its key keeps
+/// the key in `field(metadata={"json": ...})`.
+pub fn print_python_dataclass(values: &[Value], options: &Options) -> String {
+    let values: Vec<&Value> = values.iter().collect();
+    let indent = options.indent.as_deref().unwrap_or("    ");
+    let fields = merge_fields(&values, options);
+    let mut required = String::new();
+    let mut optional = String::new();
+    let keys: Vec<&str> = fields.iter().map(|(key, _)| key.as_str()).collect();
+    let mut names: Vec<String> = keys
+        .iter()
+        .map(|key| {
+            let name = snake_case(key);
+            if PYTHON_KEYWORDS.contains(&name.as_str()) {
+                format!("{}_", name)
+            } else {
+                name
+            }
+        })
+        .collect();
+    dedupe_field_names(&keys, &mut names);
+
+    for ((key, field), name) in fields.iter().zip(&names) {
+        let ty = python_field_type(&field.values);
+        let metadata =
+            (name != key).then(|| format!("metadata={{\"json\": {}}}", Value::String(key.clone())));
+        match (field.is_optional, metadata) {
+            (true, Some(metadata)) => optional.push_str(&format!(
+                "{}{}: Optional[{}] = field(default=None, {})\n",
+                indent, name, ty, metadata
+            )),
+            (true, None) => {
+                optional.push_str(&format!("{}{}: Optional[{}] = None\n", indent, name, ty))
+            }
+            (false, Some(metadata)) => required.push_str(&format!(
+                "{}{}: {} = field({})\n",
+                indent, name, ty, metadata
+            )),
+            (false, None) => required.push_str(&format!("{}{}: {}\n", indent, name, ty)),
+        }
+    }
+
+    let body = format!("{}{}", required, optional);
+    let words: BTreeSet<&str> = body
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .collect();
+    let typing: Vec<&str> = ["Any", "Dict", "List", "Optional"]
+        .into_iter()
+        .filter(|name| words.contains(name))
+        .collect();
+
+    let mut output = if body.contains("= field(") {
+        String::from("from dataclasses import dataclass, field\n")
+    } else {
+        String::from("from dataclasses import dataclass\n")
+    };
+    if !typing.is_empty() {
+        output.push_str(&format!("from typing import {}\n", typing.join(", ")));
+    }
+    output.push_str(&format!(
+        "\n\n@dataclass\nclass {}:\n",
+        options.type_name(&options.name)
+    ));
+    if body.is_empty() {
+        output.push_str(&format!("{}pass\n", indent));
+    } else {
+        output.push_str(&body);
+    }
+    output.trim_end().to_string()
+}
+
+fn python_field_type(values: &[&Value]) -> String {
+    value_field_type(values, python_value_type, "Any", |ty| {
+        format!("List[{}]", ty)
+    })
+}
+
+/// Maps a single JSON value to its Python type hint.
+pub fn python_value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "None",
+        Value::Bool(_) => "bool",
+        Value::Number(n) if n.is_f64() => "float",
+        Value::Number(_) => "int",
+        Value::String(_) => "str",
+        Value::Array(_) => "List[Any]",
+        Value::Object(_) => "Dict[str, Any]",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_print_python_dataclass() {
+        let json_value = json!([
+            { "name": "Alice", "age": 30, "is_student": false, "tags": ["a"] },
+            { "name": "Bob", "age": 25.5, "is_student": true, "tags": [], "address": null }
+        ]);
+
+        let expected_output = "from dataclasses import dataclass\nfrom typing import Any, List, Optional\n\n\n@dataclass\nclass Data:\n    age: float\n    is_student: bool\n    name: str\n    tags: List[str]\n    address: Optional[Any] = None";
+
+        assert_eq!(
+            print_python_dataclass(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_python_dataclass_renamed_fields() {
+        let json_value = json!([
+            { "created-at": "today", "from": "Alice", "userId": 1, "user_id": 2 },
+            { "created-at": "today", "from": null, "userId": 1, "user_id": 2 }
+        ]);
+
+        let expected_output = "from dataclasses import dataclass, field\nfrom typing import Optional\n\n\n@dataclass\nclass Data:\n    created_at: str = field(metadata={\"json\": \"created-at\"})\n    user_id_2: int = field(metadata={\"json\": \"userId\"})\n    user_id: int\n    from_: Optional[str] = field(default=None, metadata={\"json\": \"from\"})";
+
+        assert_eq!(
+            print_python_dataclass(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+}
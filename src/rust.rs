@@ -0,0 +1,3177 @@
+//! Rust struct generation.
+
+use crate::fields::{array_elements, merge_fields, Field};
+use crate::naming::{
+    dedupe_field_names, pascal_case, rust_field_name, rust_original_field_name, variant_name,
+};
+use crate::Options;
+use chrono::{DateTime, NaiveDate};
+use serde_json::{Map, Number, Value};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use url::Url;
+
+/// Maps a single JSON value to the Rust type used for it when no structure is inferred.
+pub fn rust_value_type(value: &Value) -> String {
+    match value {
+        Value::String(_) => "String".to_string(),
+        Value::Number(n) if n.is_i64() => "i64".to_string(),
+        Value::Number(n) if n.is_u64() => "u64".to_string(),
+        Value::Number(_) => "f64".to_string(),
+        Value::Bool(_) => "bool".to_string(),
+        Value::Array(_) => "Vec<Value>".to_string(),
+        Value::Object(_) => "HashMap<String, Value>".to_string(),
+        Value::Null => "Value".to_string(),
+    }
+}
+
+/// Numeric types ordered from narrowest to widest for promotion.
+const RUST_NUMERIC_TYPES: [&str; 4] = ["u64", "i64", "f64", "rust_decimal::Decimal"];
+
+/// Largest integer below which every integer is exactly representable as an `f64`.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+/// Detects integers too large for `i64` and `u64`, which serde_json parses as a
+/// rounded `f64`.
+fn is_lossy_number(value: &Value) -> bool {
+    value
+        .as_f64()
+        .is_some_and(|n| value.is_f64() && n.fract() == 0.0 && n.abs() >= MAX_SAFE_INTEGER)
+}
+
+/// Splits a number's text into its sign, significant digits and exponent, so that
+/// texts such as `1e2` and `100.0` compare equal.
+fn decimal_parts(text: &str) -> (bool, String, i64) {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+    let (mantissa, mut exponent) = match text.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse().unwrap_or(0)),
+        None => (text, 0),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    exponent -= fraction.len() as i64;
+    let digits = format!("{}{}", integer, fraction);
+    let digits = digits.trim_start_matches('0');
+    let significant = digits.trim_end_matches('0');
+    exponent += (digits.len() - significant.len()) as i64;
+    if significant.is_empty() {
+        return (false, String::new(), 0);
+    }
+    (negative, significant.to_string(), exponent)
+}
+
+/// Finds the numbers in a JSON text that don't read back as written once parsed:
+/// integers too large for `i64` and `u64`, and numbers with more precision than an
+/// `f64` holds, such as `0.12345678901234567890`. Returns their original text.
+pub fn inexact_numbers(json: &str) -> Vec<String> {
+    let mut numbers = Vec::new();
+    let mut chars = json.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c == '"' {
+            while let Some((_, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '"' => break,
+                    _ => {}
+                }
+            }
+            continue;
+        }
+        if c != '-' && !c.is_ascii_digit() {
+            continue;
+        }
+        let mut end = start + 1;
+        while let Some(&(i, c)) = chars.peek() {
+            if !matches!(c, '0'..='9' | '+' | '-' | '.' | 'e' | 'E') {
+                break;
+            }
+            end = i + 1;
+            chars.next();
+        }
+        let text = &json[start..end];
+        if text.parse::<i64>().is_ok() || text.parse::<u64>().is_ok() {
+            continue;
+        }
+        // Parsed the way `serde_json` parses the whole document, which can round
+        // differently from `str::parse`.
+        let read = serde_json::from_str::<Number>(text).ok();
+        if read.is_some_and(|read| decimal_parts(&read.to_string()) != decimal_parts(text)) {
+            numbers.push(text.to_string());
+        }
+    }
+    numbers
+}
+
+/// Lists the fields, as dotted paths, holding numbers that can't be represented
+/// exactly by any of the default numeric types. `inexact` holds the original text of
+/// such numbers, as found by [`inexact_numbers`], so their fields can be named;
+/// without it only integers too large for `i64` and `u64` are recognised. Those are
+/// left out with `--decimal`, which types them as `rust_decimal::Decimal`.
+pub fn precision_warnings(values: &[Value], inexact: &[String], options: &Options) -> Vec<String> {
+    let values: Vec<&Value> = values.iter().collect();
+    let inexact: HashMap<u64, &str> = inexact
+        .iter()
+        .filter_map(|text| {
+            let n = serde_json::from_str::<f64>(text).ok()?;
+            Some((n.to_bits(), text.as_str()))
+        })
+        .collect();
+    let mut warnings = Vec::new();
+    collect_precision_warnings("", &values, &inexact, options, &mut warnings);
+    warnings
+}
+
+fn collect_precision_warnings(
+    prefix: &str,
+    values: &[&Value],
+    inexact: &HashMap<u64, &str>,
+    options: &Options,
+    warnings: &mut Vec<String>,
+) {
+    for (key, field) in merge_fields(values, options) {
+        let path = format!("{}{}", prefix, key);
+        let mut values = field.values;
+        while values.iter().any(|value| value.is_array()) {
+            values = array_elements(&values);
+        }
+        let original = |value: &Value| {
+            let n = value.as_f64().filter(|_| value.is_f64())?;
+            inexact.get(&n.to_bits()).copied()
+        };
+        let integer = values
+            .iter()
+            .find(|value| is_lossy_number(value))
+            .filter(|_| !options.rust.decimal);
+        if let Some(value) = integer {
+            let text = original(value).map_or_else(|| value.to_string(), str::to_string);
+            warnings.push(format!(
+                "field `{}` holds {}, which doesn't fit in i64 or u64 and loses precision as \
+                 f64; consider --decimal for rust_decimal::Decimal",
+                path, text
+            ));
+        } else if let Some((value, text)) = values
+            .iter()
+            .filter(|value| !is_lossy_number(value))
+            .find_map(|value| Some((value, original(value)?)))
+        {
+            warnings.push(format!(
+                "field `{}` holds {}, which reads as {} in an f64; consider a decimal type, e.g. \
+                 --type-override {}=rust_decimal::Decimal",
+                path, text, value, key
+            ));
+        }
+        let objects: Vec<&Value> = values.into_iter().filter(|v| v.is_object()).collect();
+        if !objects.is_empty() {
+            collect_precision_warnings(&format!("{}.", path), &objects, inexact, options, warnings);
+        }
+    }
+}
+
+/// Types that are deserialized from JSON strings. A mix of them falls back to `String`.
+const RUST_STRING_TYPES: [&str; 5] = [
+    "String",
+    "chrono::DateTime<chrono::Utc>",
+    "chrono::NaiveDate",
+    "uuid::Uuid",
+    "url::Url",
+];
+
+/// Checks that `s` parses as an absolute URL with a host, such as
+/// `https://example.com/a`, or is a `mailto:` link. Other strings that happen to parse,
+/// like `note:1`, are left alone.
+fn is_url(s: &str) -> bool {
+    Url::parse(s).is_ok_and(|url| url.has_host() || url.scheme() == "mailto")
+}
+
+/// Shortest string treated as base64, so that short words aren't mistaken for it.
+const MIN_BASE64_LEN: usize = 16;
+
+/// Checks that `s` looks like padded standard base64 of some binary data: at least
+/// `MIN_BASE64_LEN` characters from the base64 alphabet, a multiple of four long, with
+/// `=` only as trailing padding, and at least one character that isn't a letter so
+/// long plain words don't qualify.
+fn is_base64(s: &str) -> bool {
+    let data = s.trim_end_matches('=');
+    s.len() >= MIN_BASE64_LEN
+        && s.len().is_multiple_of(4)
+        && s.len() - data.len() <= 2
+        && data
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/')
+        && s.chars().any(|c| !c.is_ascii_alphabetic())
+}
+
+/// Checks that `s` is a number written as a JSON number would be, e.g. `30` or `-1.5`,
+/// with no surrounding whitespace.
+fn is_numeric_string(s: &str) -> bool {
+    s.trim() == s && serde_json::from_str::<Number>(s).is_ok()
+}
+
+/// Returns the numbers held by `values` when every one that isn't `null` is a numeric
+/// string, and there is at least one.
+fn numeric_strings(values: &[&Value]) -> Option<Vec<Value>> {
+    let numbers: Option<Vec<Value>> = values
+        .iter()
+        .filter(|value| !value.is_null())
+        .map(|value| {
+            value
+                .as_str()
+                .filter(|s| is_numeric_string(s))
+                .and_then(|s| serde_json::from_str(s).ok())
+        })
+        .collect();
+    numbers.filter(|numbers| !numbers.is_empty())
+}
+
+/// Checks for the canonical hyphenated UUID form, e.g.
+/// `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+fn is_uuid(s: &str) -> bool {
+    let groups: Vec<&str> = s.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Merges the types observed for a single value, promoting numbers to the widest
+/// type seen (`u64` + `i64` is `i64`, anything with `f64` is `f64`) and mixed string
+/// types to `String`. Returns `None` when the types can't be reconciled.
+fn unify_rust_types(types: impl IntoIterator<Item = String>) -> Option<String> {
+    let mut types = types.into_iter();
+    let mut unified = types.next()?;
+    for ty in types {
+        if ty == unified {
+            continue;
+        }
+        let rank = |ty: &str| RUST_NUMERIC_TYPES.iter().position(|numeric| *numeric == ty);
+        let is_string = |ty: &str| RUST_STRING_TYPES.contains(&ty);
+        match (rank(&unified), rank(&ty)) {
+            (Some(a), Some(b)) => unified = RUST_NUMERIC_TYPES[a.max(b)].to_string(),
+            _ if is_string(&unified) && is_string(&ty) => unified = "String".to_string(),
+            _ => return None,
+        }
+    }
+    (unified != "Value").then_some(unified)
+}
+
+/// Returns the smallest and largest integer among `values`, or `None` if any of them
+/// isn't an `i64`.
+fn int_range(values: &[&Value]) -> Option<(i64, i64)> {
+    values
+        .iter()
+        .try_fold((i64::MAX, i64::MIN), |(min, max), value| {
+            let n = value.as_i64()?;
+            Some((min.min(n), max.max(n)))
+        })
+}
+
+/// Names of types the generated code uses, which a struct named after a key such as
+/// `value` or `string` would shadow. `Self` can't name a struct at all.
+const RESERVED_TYPE_NAMES: [&str; 9] = [
+    "Value", "String", "Option", "Vec", "HashMap", "Box", "Rc", "Arc", "Self",
+];
+
+/// Types that getters return by value rather than by reference.
+const COPY_TYPES: [&str; 6] = ["bool", "i32", "i64", "u64", "f32", "f64"];
+
+/// Whether `n` is still the same number after a round trip through `f32`, comparing
+/// the shortest decimal forms so that values like `0.1`, which neither type stores
+/// exactly, count as fitting.
+fn fits_f32(n: f64) -> bool {
+    (n as f32).to_string().parse::<f64>() == Ok(n)
+}
+
+/// How `null` values and missing keys map to `Option` in the generated structs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NullPolicy {
+    /// A key that is missing or `null` in any object is an `Option`.
+    #[default]
+    Optional,
+    /// Only missing keys make a field an `Option`. Keys that are sometimes `null`
+    /// keep their concrete type, assuming the nulls won't occur when deserializing.
+    Value,
+    /// Missing keys and `null` values are told apart: a key that is both sometimes
+    /// missing and sometimes `null` is an `Option<Option<T>>`, where the outer `None`
+    /// means missing and `Some(None)` means `null`.
+    Strict,
+}
+
+impl NullPolicy {
+    /// How many `Option`s wrap the type of `field`.
+    fn option_depth(self, field: &Field) -> usize {
+        match self {
+            NullPolicy::Optional => usize::from(field.is_optional),
+            NullPolicy::Value => usize::from(field.is_missing),
+            NullPolicy::Strict => usize::from(field.is_missing) + usize::from(field.is_nullable),
+        }
+    }
+}
+
+impl FromStr for NullPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "optional" => Ok(NullPolicy::Optional),
+            "value" => Ok(NullPolicy::Value),
+            "strict" => Ok(NullPolicy::Strict),
+            _ => Err(format!(
+                "Invalid null policy '{}'. Please use 'optional', 'value' or 'strict'.",
+                s
+            )),
+        }
+    }
+}
+
+/// Reference-counted pointer that nested structs are wrapped in for cheap cloning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SharedPointer {
+    Rc,
+    Arc,
+}
+
+impl SharedPointer {
+    fn name(self) -> &'static str {
+        match self {
+            SharedPointer::Rc => "Rc",
+            SharedPointer::Arc => "Arc",
+        }
+    }
+}
+
+impl FromStr for SharedPointer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rc" => Ok(SharedPointer::Rc),
+            "arc" => Ok(SharedPointer::Arc),
+            _ => Err(format!(
+                "Invalid shared pointer '{}'. Please use 'rc' or 'arc'.",
+                s
+            )),
+        }
+    }
+}
+
+/// How the enum that `split_shapes` generates tells its variants apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TagStyle {
+    /// `#[serde(tag = "type")]`, with a variant per value of a discriminator key that
+    /// every object holds a string for. Falls back to `Untagged` when there is none.
+    #[default]
+    Internal,
+    /// `#[serde(untagged)]`, with a variant per distinct set of keys.
+    Untagged,
+}
+
+impl FromStr for TagStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "internal" => Ok(TagStyle::Internal),
+            "untagged" => Ok(TagStyle::Untagged),
+            _ => Err(format!(
+                "Invalid tag style '{}'. Please use 'internal' or 'untagged'.",
+                s
+            )),
+        }
+    }
+}
+
+/// Keys tried, in order, as the discriminator of a tagged enum when none is given.
+const TAG_KEYS: [&str; 7] = ["type", "kind", "tag", "event", "object", "@type", "$type"];
+
+/// Groups `values` by their string value for `key`, in order of first appearance.
+/// Returns `None` unless every object holds a string for `key` and there are at
+/// least two distinct ones.
+fn tag_groups<'v>(values: &[&'v Value], key: &str) -> Option<Vec<(&'v str, Vec<&'v Value>)>> {
+    let mut groups: Vec<(&str, Vec<&Value>)> = Vec::new();
+    for value in values {
+        let tag = value.get(key)?.as_str()?;
+        match groups.iter_mut().find(|(existing, _)| *existing == tag) {
+            Some((_, objects)) => objects.push(value),
+            None => groups.push((tag, vec![value])),
+        }
+    }
+    (groups.len() >= 2).then_some(groups)
+}
+
+/// A way to type a field whose values allow more than one reasonable type, picked
+/// per key with `RustOptions::resolutions`, e.g. by `--interactive`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// `serde_json::Value`, for values of conflicting types.
+    Value,
+    /// An untagged enum with a variant per type of value, as with `--conflict-enums`.
+    UntaggedEnum,
+    /// A plain `String`, for strings taking only a few distinct values.
+    String,
+    /// An enum with a variant per distinct string, as with `--infer-enums`.
+    Enum,
+    /// A `HashMap<String, T>`, for objects whose values all share a type.
+    HashMap,
+    /// A struct with a field per key.
+    Struct,
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self {
+            Resolution::Value => "serde_json::Value",
+            Resolution::UntaggedEnum => "an untagged enum of the observed types",
+            Resolution::String => "String",
+            Resolution::Enum => "an enum of the observed values",
+            Resolution::HashMap => "HashMap<String, T>",
+            Resolution::Struct => "a struct",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+/// Longest example, in characters, shown in a `--with-examples` doc comment.
+const MAX_EXAMPLE_LEN: usize = 40;
+
+/// Renders the first non-null value as JSON for a doc comment. Strings are truncated
+/// before they are escaped, everything else after it is serialized.
+pub(crate) fn example_value(values: &[&Value]) -> Option<String> {
+    let truncate = |s: &str| -> Option<String> {
+        (s.chars().count() > MAX_EXAMPLE_LEN).then(|| s.chars().take(MAX_EXAMPLE_LEN).collect())
+    };
+    let example = match values.iter().find(|value| !value.is_null())? {
+        Value::String(s) => match truncate(s) {
+            Some(truncated) => format!("{}...", Value::String(truncated)),
+            None => Value::String(s.clone()).to_string(),
+        },
+        value => {
+            let json = value.to_string();
+            match truncate(&json) {
+                Some(truncated) => format!("{}...", truncated),
+                None => json,
+            }
+        }
+    };
+    Some(example)
+}
+
+/// Derives emitted for `--derive serde`.
+const SERDE_DERIVES: [&str; 4] = ["Debug", "Clone", "Serialize", "Deserialize"];
+
+/// Options that control how Rust structs are generated.
+#[derive(Debug, Clone, Default)]
+pub struct RustOptions {
+    /// Derives added to every generated type.
+    pub derives: Vec<String>,
+    /// Emits `pub struct` and `pub` fields.
+    pub public: bool,
+    /// Generates an untagged enum for fields whose observed types conflict instead
+    /// of falling back to `Value`.
+    pub conflict_enums: bool,
+    /// Types RFC 3339 date-times as `chrono::DateTime<Utc>` and plain dates as
+    /// `chrono::NaiveDate`.
+    pub chrono: bool,
+    /// Types canonical UUID strings as `uuid::Uuid`.
+    pub uuid: bool,
+    /// Adds `#[serde(default, skip_serializing_if = "Option::is_none")]` to optional
+    /// fields so absent values are omitted rather than serialized as `null`.
+    pub skip_none: bool,
+    /// Types integers as `i32` when every observed value fits in its range.
+    pub narrow_ints: bool,
+    /// Types floats as `f32` when every observed value survives the conversion, i.e.
+    /// prints back as the same number after going through `f32`.
+    pub prefer_f32: bool,
+    /// Adds a `/// e.g. ...` doc comment with the first observed value to every field.
+    pub examples: bool,
+    /// Generates an enum for string fields with at most this many distinct values.
+    pub infer_enums: Option<usize>,
+    /// Emits a `{Name}Builder` with fluent setters next to every struct.
+    pub builder: bool,
+    /// Generates a `pub` getter for every field of every struct, returning a reference,
+    /// or a copy for numbers and `bool`s. Optional fields return an `Option` of it.
+    pub accessors: bool,
+    /// Types integers too large for `i64`/`u64` as `rust_decimal::Decimal`.
+    pub decimal: bool,
+    /// Ends every struct with a `#[serde(flatten)]` map that captures unknown keys.
+    pub extra: bool,
+    /// Adds `#[serde(deny_unknown_fields)]` to every struct, so unexpected keys fail
+    /// deserialization. Ignored together with `extra`, which captures them instead.
+    pub deny_unknown: bool,
+    /// Types nested objects with at least this many distinct keys as a `HashMap`
+    /// when all their values share one type.
+    pub map_threshold: Option<usize>,
+    /// Keeps JSON keys that are valid identifiers, e.g. `firstName`, as field names
+    /// instead of converting them to snake_case.
+    pub keep_names: bool,
+    /// Replaces per-field renames with a container-level `#[serde(rename_all)]` when
+    /// every key follows the same casing convention.
+    pub rename_all: bool,
+    /// Types strings that parse as URLs as `url::Url`.
+    pub smart_strings: bool,
+    /// Generates a struct per distinct set of keys among the root objects, united by
+    /// an enum, instead of merging them all into one struct.
+    pub split_shapes: bool,
+    /// How the `split_shapes` enum is tagged.
+    pub tag_style: TagStyle,
+    /// Discriminator key of the internally tagged `split_shapes` enum. When `None`, the
+    /// first of a few common names, such as `type` and `kind`, that fits is used.
+    pub tag_key: Option<String>,
+    /// Adds `#[non_exhaustive]` to every generated struct.
+    pub non_exhaustive: bool,
+    /// Derives `Default` for every generated struct, or implements it by hand when a
+    /// field's type doesn't implement it.
+    pub default: bool,
+    /// Wraps the output in a `pub mod` with this name.
+    pub module: Option<String>,
+    /// Types strings that all look like base64 as `Vec<u8>`, decoded with
+    /// `#[serde(with = "base64")]`.
+    pub decode_base64: bool,
+    /// Types strings that all hold a number, e.g. `"30"`, as that number, parsed by a
+    /// generated `deserialize_with` function.
+    pub coerce_numeric_strings: bool,
+    /// How to type the fields with these keys, anywhere in the document, when their
+    /// values allow more than one type. Choices that don't fit a field's values are
+    /// ignored.
+    pub resolutions: Vec<(String, Resolution)>,
+    /// Key suffixes, such as `_id`, that make integer and string fields a newtype
+    /// named after the key, e.g. `struct UserId(i64);` for `user_id`. Empty to turn
+    /// newtypes off.
+    pub newtype_suffixes: Vec<String>,
+    /// Types nested objects as `HashMap<String, Value>` and arrays as `Vec<Value>`
+    /// instead of generating types for their contents.
+    pub flat: bool,
+    /// Wraps nested structs, including array elements, in `Rc` or `Arc`. The pointer
+    /// also provides the indirection a recursive struct would otherwise get from `Box`.
+    /// Deserializing them needs serde's `rc` feature.
+    pub shared: Option<SharedPointer>,
+    /// Types to use for fields with the given keys, at any depth, instead of the
+    /// inferred ones, e.g. `("price", "Decimal")`.
+    pub type_overrides: Vec<(String, String)>,
+    /// Comments every optional field with the index of the first object it was
+    /// missing from or `null` in, counting from 0 among the objects merged into the
+    /// struct.
+    pub trace_optional: bool,
+    /// Decides which fields are `Option`s.
+    pub null_policy: NullPolicy,
+    /// Implements `TryFrom<&Value>` for every generated struct, extracting each field
+    /// by hand.
+    pub try_from: bool,
+    /// Appends a `#[cfg(test)]` module that deserializes the first sample object into
+    /// the root type.
+    pub emit_tests: bool,
+    /// Starts the output with the `use` declarations for the serde derives, `Value`
+    /// and `HashMap`, as far as the generated code needs them.
+    pub imports: bool,
+}
+
+impl RustOptions {
+    /// Parses the `--derive` argument: either `serde` for the default set or a
+    /// comma-separated list of derive names.
+    pub fn set_derives(&mut self, list: &str) {
+        self.derives = if list == "serde" {
+            SERDE_DERIVES.iter().map(|d| d.to_string()).collect()
+        } else {
+            list.split(',')
+                .map(|d| d.trim().to_string())
+                .filter(|d| !d.is_empty())
+                .collect()
+        };
+    }
+
+    /// Parses the `--type-override` argument, a comma-separated list of `key=Type`
+    /// pairs. Commas inside angle brackets, as in `HashMap<String, i64>`, belong to
+    /// the type.
+    pub fn set_type_overrides(&mut self, list: &str) -> Result<(), String> {
+        let mut entries = Vec::new();
+        let mut depth = 0;
+        let mut start = 0;
+        for (i, c) in list.char_indices() {
+            match c {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                ',' if depth == 0 => {
+                    entries.push(&list[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        entries.push(&list[start..]);
+
+        for entry in entries.into_iter().filter(|entry| !entry.trim().is_empty()) {
+            match entry.split_once('=') {
+                Some((key, ty)) if !key.trim().is_empty() && !ty.trim().is_empty() => self
+                    .type_overrides
+                    .push((key.trim().to_string(), ty.trim().to_string())),
+                _ => {
+                    return Err(format!(
+                        "Invalid type override '{}'. Use key=Type, e.g. price=Decimal.",
+                        entry.trim()
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Accumulates the generated structs, in declaration order, while walking the input.
+struct RustGenerator<'a> {
+    options: &'a Options,
+    indent: &'a str,
+    structs: Vec<(String, String)>,
+    /// The fields of every struct generated so far, keyed by struct name, so that
+    /// structurally identical objects can share one type.
+    shapes: Vec<(String, String)>,
+    /// The structs currently being generated, outermost first, so that objects nested
+    /// inside one with the same shape can refer back to it.
+    ancestors: Vec<Ancestor>,
+    /// Generated types that don't implement `Default`, with the expression used for
+    /// them in a hand-written `Default` impl.
+    no_default: Vec<(String, String)>,
+    /// Set once a field is parsed from numeric strings, so the functions doing it can
+    /// be emitted.
+    uses_numeric_strings: bool,
+}
+
+/// A struct whose fields are still being generated.
+struct Ancestor {
+    name: String,
+    signature: Vec<(String, Vec<&'static str>)>,
+    /// Keys that are missing or `null` in the nested objects of the same shape, which
+    /// makes them optional in the struct as well.
+    nullable: HashSet<String>,
+}
+
+/// Describes the shape of merged objects as their sorted keys, each with the kinds of
+/// JSON values observed for it apart from `null`.
+fn shape_signature(fields: &[(String, Field)]) -> Vec<(String, Vec<&'static str>)> {
+    let mut signature: Vec<(String, Vec<&'static str>)> = fields
+        .iter()
+        .map(|(key, field)| (key.clone(), field.observed_kinds()))
+        .collect();
+    signature.sort_by(|(a, _), (b, _)| a.cmp(b));
+    signature
+}
+
+impl<'a> RustGenerator<'a> {
+    fn new(options: &'a Options) -> Self {
+        RustGenerator {
+            options,
+            indent: options.indent.as_deref().unwrap_or("    "),
+            structs: Vec::new(),
+            shapes: Vec::new(),
+            ancestors: Vec::new(),
+            no_default: Vec::new(),
+            uses_numeric_strings: false,
+        }
+    }
+
+    /// Returns the name of the enclosing struct that `values` has the same shape as,
+    /// if any: the same keys holding the same kinds of values, where a key that is
+    /// always `null` on one side matches anything. Its optional keys are recorded so
+    /// the recursive struct covers them too.
+    fn recursive_type(&mut self, values: &[&Value]) -> Option<String> {
+        let fields = merge_fields(values, self.options);
+        let signature = shape_signature(&fields);
+        let ancestor =
+            self.ancestors.iter_mut().rev().find(|ancestor| {
+                !signature.is_empty()
+                    && ancestor.signature.len() == signature.len()
+                    && ancestor.signature.iter().zip(&signature).all(|(a, b)| {
+                        a.0 == b.0 && (a.1 == b.1 || a.1.is_empty() || b.1.is_empty())
+                    })
+            })?;
+        for (key, field) in &fields {
+            if self.options.rust.null_policy.option_depth(field) > 0 {
+                ancestor.nullable.insert(key.clone());
+            }
+        }
+        Some(ancestor.name.clone())
+    }
+
+    /// Returns the type `--type-override` forces for the field `key`, if any.
+    fn type_override(&self, key: &str) -> Option<&'a str> {
+        self.options
+            .rust
+            .type_overrides
+            .iter()
+            .find(|(overridden, _)| overridden == key)
+            .map(|(_, ty)| ty.as_str())
+    }
+
+    /// Picks a struct name that hasn't been generated yet, and doesn't shadow a type
+    /// the output refers to, by appending a counter.
+    fn unique_struct_name(&self, name: &str) -> String {
+        let mut candidate = name.to_string();
+        let mut counter = 2;
+        while RESERVED_TYPE_NAMES.contains(&candidate.as_str())
+            || self
+                .structs
+                .iter()
+                .any(|(existing, _)| existing == &candidate)
+        {
+            candidate = format!("{}{}", name, counter);
+            counter += 1;
+        }
+        candidate
+    }
+
+    /// Maps a value to its Rust type, recognising formatted strings when enabled.
+    fn value_type(&self, value: &Value) -> String {
+        if let Value::String(s) = value {
+            if self.options.rust.chrono {
+                if DateTime::parse_from_rfc3339(s).is_ok() {
+                    return "chrono::DateTime<chrono::Utc>".to_string();
+                }
+                if NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() {
+                    return "chrono::NaiveDate".to_string();
+                }
+            }
+            if self.options.rust.uuid && is_uuid(s) {
+                return "uuid::Uuid".to_string();
+            }
+            if self.options.rust.smart_strings && is_url(s) {
+                return "url::Url".to_string();
+            }
+        }
+        if self.options.rust.decimal && is_lossy_number(value) {
+            return "rust_decimal::Decimal".to_string();
+        }
+        rust_value_type(value)
+    }
+
+    /// Unifies the types of scalar values, narrowing integers to `i32` and floats to
+    /// `f32` when enabled and every value fits.
+    fn scalar_type(&self, values: &[&Value]) -> Option<String> {
+        let ty = unify_rust_types(values.iter().map(|value| self.value_type(value)))?;
+        let fits_i32 = |(min, max)| min >= i64::from(i32::MIN) && max <= i64::from(i32::MAX);
+        if ty == "i64" && self.options.rust.narrow_ints && int_range(values).is_some_and(fits_i32) {
+            return Some("i32".to_string());
+        }
+        if ty == "f64"
+            && self.options.rust.prefer_f32
+            && values
+                .iter()
+                .all(|value| value.as_f64().is_some_and(fits_f32))
+        {
+            return Some("f32".to_string());
+        }
+        Some(ty)
+    }
+
+    fn field_type(&mut self, key: &str, values: &[&Value]) -> String {
+        if let Some(ty) = self.type_override(key) {
+            return ty.to_string();
+        }
+        let present: Vec<&Value> = values.iter().filter(|v| !v.is_null()).copied().collect();
+        if present.is_empty() {
+            return "Value".to_string();
+        }
+        if self.options.rust.flat {
+            if present.iter().all(|value| value.is_object()) {
+                return "HashMap<String, Value>".to_string();
+            }
+            if present.iter().all(|value| value.is_array()) {
+                return "Vec<Value>".to_string();
+            }
+        }
+        let resolution = self
+            .options
+            .rust
+            .resolutions
+            .iter()
+            .find(|(resolved, _)| resolved == key)
+            .map(|(_, resolution)| *resolution);
+        if present.iter().all(|value| value.is_object()) {
+            if let Some(ty) = self.map_type(key, &present, resolution) {
+                return ty;
+            }
+            let ty = self.generate_struct(&self.options.type_name(&pascal_case(key)), &present);
+            if ty != "Value" {
+                if let Some(shared) = self.options.rust.shared {
+                    return format!("{}<{}>", shared.name(), ty);
+                }
+            }
+            // A struct directly containing itself needs indirection to have a size.
+            return if self.ancestors.iter().any(|ancestor| ancestor.name == ty) {
+                format!("Box<{}>", ty)
+            } else {
+                ty
+            };
+        }
+        if present.iter().all(|value| value.is_array()) {
+            return self.array_type(key, &present);
+        }
+        if self.options.rust.decode_base64
+            && present.iter().all(|value| {
+                value.as_str().is_some_and(is_base64) && self.value_type(value) == "String"
+            })
+        {
+            return "Vec<u8>".to_string();
+        }
+        if self.options.rust.coerce_numeric_strings {
+            if let Some(numbers) = numeric_strings(&present) {
+                let numbers: Vec<&Value> = numbers.iter().collect();
+                if let Some(ty) = self.scalar_type(&numbers) {
+                    return ty;
+                }
+            }
+        }
+        let max_variants = match resolution {
+            Some(Resolution::Enum) => Some(usize::MAX),
+            Some(Resolution::String) => None,
+            _ => self.options.rust.infer_enums,
+        };
+        if let Some(max_variants) = max_variants {
+            if let Some(name) = self.generate_string_enum(key, &present, max_variants) {
+                return name;
+            }
+        }
+        if !present
+            .iter()
+            .any(|value| value.is_object() || value.is_array())
+        {
+            if let Some(ty) = self.scalar_type(&present) {
+                return self.newtype(key, ty);
+            }
+        }
+
+        // The observed values can't share a single type, so fall back to something
+        // that deserializes all of them.
+        let conflict_enums = match resolution {
+            Some(Resolution::UntaggedEnum) => true,
+            Some(Resolution::Value) => false,
+            _ => self.options.rust.conflict_enums,
+        };
+        if conflict_enums {
+            self.generate_enum(key, &present)
+        } else {
+            "Value".to_string()
+        }
+    }
+
+    /// Wraps the scalar type `ty` of the field `key` in a newtype named after the key
+    /// when the key ends in one of the newtype suffixes. Fields with the same key share
+    /// one newtype.
+    fn newtype(&mut self, key: &str, ty: String) -> String {
+        let is_newtype = self
+            .options
+            .rust
+            .newtype_suffixes
+            .iter()
+            .any(|suffix| key.len() > suffix.len() && key.ends_with(suffix.as_str()));
+        if !is_newtype || !["i32", "i64", "u64", "String"].contains(&ty.as_str()) {
+            return ty;
+        }
+
+        let visibility = if self.options.rust.public { "pub " } else { "" };
+        let name = self.options.type_name(&pascal_case(key));
+        let body = |name: &str| {
+            format!(
+                "{}{}struct {}({}{});",
+                self.derive_attribute(self.options.rust.default),
+                visibility,
+                name,
+                visibility,
+                ty
+            )
+        };
+        if let Some((existing, _)) = self
+            .structs
+            .iter()
+            .find(|(existing, existing_body)| *existing_body == body(existing))
+            .filter(|(existing, _)| existing.starts_with(&name))
+        {
+            return existing.clone();
+        }
+        let name = self.unique_struct_name(&name);
+        let body = body(&name);
+        self.structs.push((name.clone(), body));
+        name
+    }
+
+    /// Types dictionary-like objects, with many distinct keys but uniform values, as
+    /// `HashMap<String, T>` when `--map-threshold` is set, or when `resolution` asks for
+    /// a map.
+    fn map_type(
+        &mut self,
+        key: &str,
+        objects: &[&Value],
+        resolution: Option<Resolution>,
+    ) -> Option<String> {
+        let threshold = match resolution {
+            Some(Resolution::HashMap) => 1,
+            Some(Resolution::Struct) => return None,
+            _ => self.options.rust.map_threshold?,
+        };
+        let maps: Vec<&Map<String, Value>> = objects
+            .iter()
+            .filter_map(|value| value.as_object())
+            .collect();
+        let keys: HashSet<&String> = maps.iter().flat_map(|map| map.keys()).collect();
+        if keys.len() < threshold {
+            return None;
+        }
+
+        let values: Vec<&Value> = maps.iter().flat_map(|map| map.values()).collect();
+        let present: Vec<&Value> = values.iter().filter(|v| !v.is_null()).copied().collect();
+        let ty = if !present.is_empty() && present.iter().all(|value| value.is_object()) {
+            self.generate_struct(&self.options.type_name(&pascal_case(key)), &present)
+        } else if present
+            .iter()
+            .any(|value| value.is_object() || value.is_array())
+        {
+            return None;
+        } else {
+            self.scalar_type(&present)?
+        };
+        if present.len() < values.len() {
+            Some(format!("HashMap<String, Option<{}>>", ty))
+        } else {
+            Some(format!("HashMap<String, {}>", ty))
+        }
+    }
+
+    /// Generates an untagged enum with one variant per kind of value observed for a
+    /// field whose types conflict. Returns the name given to the enum.
+    fn generate_enum(&mut self, key: &str, values: &[&Value]) -> String {
+        let name = self.unique_struct_name(
+            &self
+                .options
+                .type_name(&format!("{}Field", pascal_case(key))),
+        );
+        let index = self.structs.len();
+        self.structs.push((name.clone(), String::new()));
+
+        let of_kind = |predicate: fn(&Value) -> bool| -> Vec<&Value> {
+            values.iter().filter(|v| predicate(v)).copied().collect()
+        };
+        let mut variants = Vec::new();
+        if values.iter().any(|value| value.is_boolean()) {
+            variants.push(("Bool", "bool".to_string()));
+        }
+        let numbers = of_kind(Value::is_number);
+        if let Some(ty) = unify_rust_types(numbers.iter().map(|v| rust_value_type(v))) {
+            variants.push(("Number", ty));
+        }
+        if values.iter().any(|value| value.is_string()) {
+            variants.push(("String", "String".to_string()));
+        }
+        let arrays = of_kind(Value::is_array);
+        if !arrays.is_empty() {
+            variants.push(("Array", self.array_type(key, &arrays)));
+        }
+        let objects = of_kind(Value::is_object);
+        if !objects.is_empty() {
+            variants.push((
+                "Object",
+                self.generate_struct(&self.options.type_name(&pascal_case(key)), &objects),
+            ));
+        }
+
+        if let Some((variant, _)) = variants.first() {
+            let default = format!("{}::{}(Default::default())", name, variant);
+            self.no_default.push((name.clone(), default));
+        }
+
+        let visibility = if self.options.rust.public { "pub " } else { "" };
+        let mut output = String::new();
+        for (variant, ty) in variants {
+            output.push_str(&format!("{}{}({}),\n", self.indent, variant, ty));
+        }
+        self.structs[index].1 = format!(
+            "{}#[serde(untagged)]\n{}enum {} {{\n{}}}",
+            self.derive_attribute(false),
+            visibility,
+            name,
+            output
+        );
+        name
+    }
+
+    /// Generates a unit enum for a field whose values are plain strings drawn from a
+    /// small set. Returns `None`, generating nothing, when there are more than
+    /// `max_variants` distinct values or they don't map to distinct variant names.
+    /// Fields with the same key and values share one enum, so structs that hold them
+    /// keep the same shape and can be reused too.
+    fn generate_string_enum(
+        &mut self,
+        key: &str,
+        values: &[&Value],
+        max_variants: usize,
+    ) -> Option<String> {
+        let mut variants: Vec<(String, &str)> = Vec::new();
+        for value in values {
+            let s = value.as_str()?;
+            if self.value_type(value) != "String" {
+                return None;
+            }
+            if !variants.iter().any(|(_, existing)| *existing == s) {
+                variants.push((variant_name(s), s));
+            }
+        }
+        variants.sort_by(|a, b| a.1.cmp(b.1));
+        let mut names = HashSet::new();
+        if variants.len() > max_variants || !variants.iter().all(|(name, _)| names.insert(name)) {
+            return None;
+        }
+
+        let visibility = if self.options.rust.public { "pub " } else { "" };
+        let mut output = String::new();
+        for (variant, value) in &variants {
+            if variant != value {
+                output.push_str(&format!(
+                    "{}#[serde(rename = {})]\n",
+                    self.indent,
+                    Value::String(value.to_string())
+                ));
+            }
+            output.push_str(&format!("{}{},\n", self.indent, variant));
+        }
+        let body = |name: &str| {
+            format!(
+                "{}{}enum {} {{\n{}}}",
+                self.derive_attribute(false),
+                visibility,
+                name,
+                output
+            )
+        };
+        let name = self.options.type_name(&pascal_case(key));
+        if let Some((existing, _)) = self
+            .structs
+            .iter()
+            .find(|(existing, existing_body)| *existing_body == body(existing))
+            .filter(|(existing, _)| existing.starts_with(&name))
+        {
+            return Some(existing.clone());
+        }
+        let name = self.unique_struct_name(&name);
+        let body = body(&name);
+        self.structs.push((name.clone(), body));
+        let default = format!("{}::{}", name, variants[0].0);
+        self.no_default.push((name.clone(), default));
+        Some(name)
+    }
+
+    /// Builds the `#[derive(...)]` attribute from `--derive`, adding `Default` when
+    /// `default` is set.
+    fn derive_attribute(&self, default: bool) -> String {
+        let mut derives: Vec<&str> = self
+            .options
+            .rust
+            .derives
+            .iter()
+            .map(String::as_str)
+            .collect();
+        if default && !derives.contains(&"Default") {
+            derives.push("Default");
+        }
+        if derives.is_empty() {
+            String::new()
+        } else {
+            format!("#[derive({})]\n", derives.join(", "))
+        }
+    }
+
+    /// Returns the expression a hand-written `Default` impl uses for a field of type
+    /// `ty`, or `None` when `Default::default()` works for it.
+    fn default_value(&self, ty: &str) -> Option<String> {
+        if ty == "url::Url" {
+            return Some("url::Url::parse(\"about:blank\").unwrap()".to_string());
+        }
+        self.no_default
+            .iter()
+            .find(|(name, _)| name == ty)
+            .map(|(_, default)| default.clone())
+    }
+
+    /// Hand-writes `impl Default` for a struct with fields, given as
+    /// `(name, type, is_optional)`, whose types don't all implement `Default`.
+    fn generate_default_impl(
+        &self,
+        name: &str,
+        fields: &[(String, String, bool)],
+        extra: Option<&str>,
+    ) -> String {
+        let indent = self.indent;
+        let indent2 = indent.repeat(2);
+        let indent3 = indent.repeat(3);
+        let mut initializers = String::new();
+        for (field_name, ty, is_optional) in fields {
+            let value = if *is_optional {
+                None
+            } else {
+                self.default_value(ty)
+            };
+            initializers.push_str(&format!(
+                "{}{}: {},\n",
+                indent3,
+                field_name,
+                value.as_deref().unwrap_or("Default::default()")
+            ));
+        }
+        if let Some(extra) = extra {
+            initializers.push_str(&format!("{}{}: Default::default(),\n", indent3, extra));
+        }
+        format!(
+            "impl Default for {} {{\n{}fn default() -> Self {{\n{}{} {{\n{}{}}}\n{}}}\n}}",
+            name, indent, indent2, name, initializers, indent2, indent
+        )
+    }
+
+    /// Writes an expression that extracts a `ty` from the `&Value` named `var` by hand,
+    /// evaluating to `None` when the value doesn't fit. Types without a dedicated
+    /// accessor, such as generated enums, go through `serde_json::from_value`.
+    fn extract_expr(&self, ty: &str, var: &str) -> String {
+        if let Some(inner) = ty.strip_prefix("Vec<").and_then(|t| t.strip_suffix('>')) {
+            return format!(
+                "{}.as_array().and_then(|items| items.iter().map(|item| {}).collect::<Option<Vec<_>>>())",
+                var,
+                self.extract_expr(inner, "item")
+            );
+        }
+        if let Some(inner) = ty.strip_prefix("Option<").and_then(|t| t.strip_suffix('>')) {
+            return format!(
+                "if {}.is_null() {{ Some(None) }} else {{ {}.map(Some) }}",
+                var,
+                self.extract_expr(inner, var)
+            );
+        }
+        for pointer in ["Box", "Rc", "Arc"] {
+            if let Some(inner) = ty
+                .strip_prefix(pointer)
+                .and_then(|t| t.strip_prefix('<'))
+                .and_then(|t| t.strip_suffix('>'))
+            {
+                return format!("{}.map({}::new)", self.extract_expr(inner, var), pointer);
+            }
+        }
+        match ty {
+            "String" => format!("{}.as_str().map(str::to_string)", var),
+            "bool" => format!("{}.as_bool()", var),
+            "i64" => format!("{}.as_i64()", var),
+            "u64" => format!("{}.as_u64()", var),
+            "f64" => format!("{}.as_f64()", var),
+            "i32" => format!("{}.as_i64().and_then(|n| i32::try_from(n).ok())", var),
+            "f32" => format!("{}.as_f64().map(|n| n as f32)", var),
+            "Value" | "serde_json::Value" => format!("Some({}.clone())", var),
+            _ if self.shapes.iter().any(|(name, _)| name == ty) => {
+                format!("{}::try_from({}).ok()", ty, var)
+            }
+            _ => format!("serde_json::from_value({}.clone()).ok()", var),
+        }
+    }
+
+    /// Hand-writes `impl TryFrom<&Value>` for a struct with fields, given as
+    /// `(name, type, is_optional)` along with their JSON `keys`. A missing or
+    /// mistyped required field is an error, while optional fields become `None`.
+    fn generate_try_from_impl(
+        &self,
+        name: &str,
+        fields: &[(String, String, bool)],
+        keys: &[&str],
+        extra: Option<&str>,
+    ) -> String {
+        let indent = self.indent;
+        let indent2 = indent.repeat(2);
+        let indent3 = indent.repeat(3);
+        let mut initializers = String::new();
+        for ((field_name, ty, is_optional), key) in fields.iter().zip(keys) {
+            let key = Value::String(key.to_string());
+            let value = if !*is_optional {
+                format!(
+                    "object.get({}).and_then(|v| {}).ok_or(\"missing or invalid field {}\")?",
+                    key,
+                    self.extract_expr(ty, "v"),
+                    key.as_str().unwrap_or_default().escape_default()
+                )
+            } else if ty.starts_with("Option<") {
+                format!(
+                    "object.get({}).and_then(|v| {})",
+                    key,
+                    self.extract_expr(ty, "v")
+                )
+            } else {
+                format!(
+                    "object.get({}).filter(|v| !v.is_null()).and_then(|v| {})",
+                    key,
+                    self.extract_expr(ty, "v")
+                )
+            };
+            initializers.push_str(&format!("{}{}: {},\n", indent3, field_name, value));
+        }
+        if let Some(extra) = extra {
+            let keys: Vec<String> = keys
+                .iter()
+                .map(|key| Value::String(key.to_string()).to_string())
+                .collect();
+            initializers.push_str(&format!(
+                "{}{}: object.iter().filter(|(key, _)| ![{}].contains(&key.as_str())).map(|(key, value)| (key.clone(), value.clone())).collect(),\n",
+                indent3,
+                extra,
+                keys.join(", ")
+            ));
+        }
+        format!(
+            "impl TryFrom<&Value> for {} {{\n{}type Error = String;\n\n{}fn try_from(value: &Value) -> Result<Self, Self::Error> {{\n{}let object = value.as_object().ok_or(\"expected an object\")?;\n{}Ok({} {{\n{}{}}})\n{}}}\n}}",
+            name, indent, indent, indent2, indent2, name, initializers, indent2, indent
+        )
+    }
+
+    /// Infers `Vec<T>` when every element shares a type, falling back to `Vec<Value>`
+    /// for empty or heterogeneous arrays. Arrays of arrays recurse to any depth.
+    fn array_type(&mut self, key: &str, values: &[&Value]) -> String {
+        let elements = array_elements(values);
+        if elements.is_empty() {
+            return "Vec<Value>".to_string();
+        }
+        if elements.iter().all(|value| value.is_array()) {
+            return format!("Vec<{}>", self.array_type(key, &elements));
+        }
+        if elements.iter().all(|value| value.is_object()) {
+            let ty = self.generate_struct(
+                &self
+                    .options
+                    .type_name(&pascal_case(&self.options.element_key(key))),
+                &elements,
+            );
+            return match self.options.rust.shared {
+                Some(shared) if ty != "Value" => format!("Vec<{}<{}>>", shared.name(), ty),
+                _ => format!("Vec<{}>", ty),
+            };
+        }
+
+        match self.scalar_type(&elements) {
+            Some(ty) => format!("Vec<{}>", ty),
+            None => "Vec<Value>".to_string(),
+        }
+    }
+
+    /// Generates the `builder` struct and impl for struct `name`, whose fields are
+    /// given as `(name, type, is_optional)`. Required fields are arguments to `new`
+    /// and optional ones get a setter and default to `None`. The `extra` catch-all,
+    /// if any, starts out empty.
+    fn generate_builder(
+        &self,
+        name: &str,
+        builder: &str,
+        fields: &[(String, String, bool)],
+        extra: Option<&str>,
+    ) -> String {
+        let visibility = if self.options.rust.public { "pub " } else { "" };
+        let indent = self.indent;
+        let indent2 = indent.repeat(2);
+        let indent3 = indent.repeat(3);
+
+        let mut members = String::new();
+        for (field_name, ty, is_optional) in fields {
+            let ty = if *is_optional {
+                format!("Option<{}>", ty)
+            } else {
+                ty.clone()
+            };
+            members.push_str(&format!("{}{}: {},\n", indent, field_name, ty));
+        }
+
+        let required: Vec<&(String, String, bool)> = fields.iter().filter(|f| !f.2).collect();
+        let params: Vec<String> = required
+            .iter()
+            .map(|(field_name, ty, _)| format!("{}: {}", field_name, ty))
+            .collect();
+        let mut initializers = String::new();
+        for (field_name, _, is_optional) in fields {
+            if *is_optional {
+                initializers.push_str(&format!("{}{}: None,\n", indent3, field_name));
+            } else {
+                initializers.push_str(&format!("{}{},\n", indent3, field_name));
+            }
+        }
+
+        let mut methods = format!(
+            "{}{}fn new({}) -> Self {{\n{}{} {{\n{}{}}}\n{}}}\n",
+            indent,
+            visibility,
+            params.join(", "),
+            indent2,
+            builder,
+            initializers,
+            indent2,
+            indent
+        );
+        for (field_name, ty, _) in fields.iter().filter(|f| f.2) {
+            methods.push_str(&format!(
+                "\n{i}{v}fn {f}(mut self, {f}: {t}) -> Self {{\n{i2}self.{f} = Some({f});\n{i2}self\n{i}}}\n",
+                i = indent,
+                i2 = indent2,
+                v = visibility,
+                f = field_name,
+                t = ty
+            ));
+        }
+        let mut moves = String::new();
+        for (field_name, _, _) in fields {
+            moves.push_str(&format!(
+                "{}{}: self.{},\n",
+                indent3, field_name, field_name
+            ));
+        }
+        if let Some(extra) = extra {
+            moves.push_str(&format!("{}{}: HashMap::new(),\n", indent3, extra));
+        }
+        methods.push_str(&format!(
+            "\n{}{}fn build(self) -> {} {{\n{}{} {{\n{}{}}}\n{}}}\n",
+            indent, visibility, name, indent2, name, moves, indent2, indent
+        ));
+
+        let allow = allow_non_snake_case(fields);
+        format!(
+            "{}{}struct {} {{\n{}}}\n\n{}impl {} {{\n{}}}",
+            allow, visibility, builder, members, allow, builder, methods
+        )
+    }
+
+    /// Generates a struct for the given objects along with any nested structs it
+    /// needs. Returns the name given to the struct, or to an enclosing struct when the
+    /// objects have the same shape as it, as in a tree of nodes. Objects nested deeper
+    /// than `--max-depth` are typed as `Value` instead.
+    fn generate_struct(&mut self, name: &str, values: &[&Value]) -> String {
+        if self
+            .options
+            .max_depth
+            .is_some_and(|max| self.ancestors.len() > max)
+        {
+            return "Value".to_string();
+        }
+        if let Some(name) = self.recursive_type(values) {
+            return name;
+        }
+        let name = self.unique_struct_name(name);
+        let index = self.structs.len();
+        self.structs.push((name.clone(), String::new()));
+        if self.options.rust.builder {
+            let builder = self.unique_struct_name(&format!("{}Builder", name));
+            self.structs.push((builder, String::new()));
+        }
+
+        let visibility = if self.options.rust.public { "pub " } else { "" };
+        let mut output = String::new();
+        let mut fields = Vec::new();
+        let mut keys = Vec::new();
+        let merged = merge_fields(values, self.options);
+        self.ancestors.push(Ancestor {
+            name: name.clone(),
+            signature: shape_signature(&merged),
+            nullable: HashSet::new(),
+        });
+        let mut types = Vec::new();
+        for (key, field) in &merged {
+            types.push(if field.is_always_null {
+                "serde_json::Value".to_string()
+            } else {
+                self.field_type(key, &field.values)
+            });
+        }
+        let ancestor = self.ancestors.pop().expect("pushed above");
+
+        let mut field_names: Vec<String> = merged
+            .iter()
+            .map(|(key, _)| {
+                if self.options.rust.keep_names {
+                    rust_original_field_name(key)
+                } else {
+                    rust_field_name(key)
+                }
+            })
+            .collect();
+        let merged_keys: Vec<&str> = merged.iter().map(|(key, _)| key.as_str()).collect();
+        dedupe_field_names(&merged_keys, &mut field_names);
+        let rename_all = if self.options.rust.rename_all {
+            rename_all_rule(merged.iter().map(|(key, _)| key).zip(&field_names))
+        } else {
+            None
+        };
+
+        for (((key, field), ty), field_name) in merged.iter().zip(types).zip(field_names) {
+            let depth = self.options.rust.null_policy.option_depth(field);
+            let is_optional = depth > 0 || ancestor.nullable.contains(key);
+            // The inner `Option` of a double option is part of the type, so the
+            // builder and `Default` treat the field like any other optional one.
+            let ty = if depth > 1 {
+                format!("Option<{}>", ty)
+            } else {
+                ty
+            };
+            if field.is_always_null {
+                output.push_str(&format!(
+                    "{}// Always null in the input, so the real type is unknown.\n",
+                    self.indent
+                ));
+            } else if ty == "Value" && field.observed_kinds().len() > 1 {
+                output.push_str(&format!(
+                    "{}// observed: {}\n",
+                    self.indent,
+                    field.observed_kinds().join(", ")
+                ));
+            }
+            if self.options.rust.trace_optional && is_optional {
+                let mut reasons = Vec::new();
+                if let Some(index) = field.first_missing {
+                    reasons.push(format!("missing from object {}", index));
+                }
+                if let Some(index) = field.first_null {
+                    reasons.push(format!("null in object {}", index));
+                }
+                if !reasons.is_empty() {
+                    output.push_str(&format!(
+                        "{}// optional: first {}\n",
+                        self.indent,
+                        reasons.join(", first ")
+                    ));
+                }
+            }
+            if self.options.rust.examples {
+                if let Some(example) = example_value(&field.values) {
+                    output.push_str(&format!("{}/// e.g. {}\n", self.indent, example));
+                }
+            }
+            if rename_all.is_none() && field_name.trim_start_matches("r#") != key {
+                output.push_str(&format!(
+                    "{}#[serde(rename = {})]\n",
+                    self.indent,
+                    Value::String(key.clone())
+                ));
+            }
+            if ty == "Vec<u8>" && self.options.rust.decode_base64 {
+                let module = if is_optional {
+                    "base64::option"
+                } else {
+                    "base64"
+                };
+                output.push_str(&format!("{}#[serde(with = \"{}\")]\n", self.indent, module));
+            }
+            if self.options.rust.coerce_numeric_strings
+                && ty != "Value"
+                && self.type_override(key).is_none()
+                && numeric_strings(&field.values).is_some()
+            {
+                self.uses_numeric_strings = true;
+                // `skip_none` below adds `default` itself, and it can only appear once.
+                let default = if is_optional && !self.options.rust.skip_none {
+                    "default, "
+                } else {
+                    ""
+                };
+                output.push_str(&format!(
+                    "{}#[serde({}deserialize_with = \"{}\")]\n",
+                    self.indent,
+                    default,
+                    if is_optional {
+                        "from_optional_numeric_string"
+                    } else {
+                        "from_numeric_string"
+                    }
+                ));
+            }
+            if depth > 1 {
+                output.push_str(&format!(
+                    "{}#[serde(default, with = \"::serde_with::rust::double_option\")]\n",
+                    self.indent
+                ));
+            } else if is_optional && self.options.rust.skip_none {
+                output.push_str(&format!(
+                    "{}#[serde(default, skip_serializing_if = \"Option::is_none\")]\n",
+                    self.indent
+                ));
+            }
+            output.push_str(&format!("{}{}{}: ", self.indent, visibility, field_name));
+            output.push_str(if is_optional { "Option<" } else { "" });
+            output.push_str(&ty);
+            output.push_str(if is_optional { ">" } else { "" });
+            output.push_str(",\n");
+            fields.push((field_name, ty, is_optional));
+            keys.push(key.as_str());
+        }
+        let extra = self.options.rust.extra.then(|| {
+            let mut extra = "extra".to_string();
+            while fields.iter().any(|(field_name, _, _)| *field_name == extra) {
+                extra.push('_');
+            }
+            output.push_str(&format!(
+                "{}#[serde(flatten)]\n{}{}{}: HashMap<String, Value>,\n",
+                self.indent, self.indent, visibility, extra
+            ));
+            extra
+        });
+
+        // Nested structs are generated, and deduplicated, before their parent, so a
+        // matching body means the whole shape has been seen before. Drop the slots
+        // reserved for this struct and reuse the first type instead.
+        if let Some((existing, _)) = self.shapes.iter().find(|(_, shape)| *shape == output) {
+            let existing = existing.clone();
+            self.structs.truncate(index);
+            return existing;
+        }
+        self.shapes.push((name.clone(), output.clone()));
+        if self.options.rust.builder {
+            let builder = self.structs[index + 1].0.clone();
+            self.structs[index + 1].1 =
+                self.generate_builder(&name, &builder, &fields, extra.as_deref());
+        }
+
+        let default = self.options.rust.default;
+        let derive_default = default
+            && fields
+                .iter()
+                .all(|(_, ty, is_optional)| *is_optional || self.default_value(ty).is_none());
+        self.structs[index].1 = format!(
+            "{}{}{}{}{}{}struct {} {{\n{}}}",
+            self.derive_attribute(derive_default),
+            rename_all
+                .map(|rule| format!("#[serde(rename_all = \"{}\")]\n", rule))
+                .unwrap_or_default(),
+            if self.options.rust.deny_unknown && extra.is_none() {
+                "#[serde(deny_unknown_fields)]\n"
+            } else {
+                ""
+            },
+            allow_non_snake_case(&fields),
+            if self.options.rust.non_exhaustive {
+                "#[non_exhaustive]\n"
+            } else {
+                ""
+            },
+            visibility,
+            name,
+            output
+        );
+        if default && !derive_default {
+            let default_impl = self.generate_default_impl(&name, &fields, extra.as_deref());
+            self.structs[index].1.push_str("\n\n");
+            self.structs[index].1.push_str(&default_impl);
+        }
+        if self.options.rust.try_from {
+            let try_from = self.generate_try_from_impl(&name, &fields, &keys, extra.as_deref());
+            self.structs[index].1.push_str("\n\n");
+            self.structs[index].1.push_str(&try_from);
+        }
+        if self.options.rust.accessors && !(fields.is_empty() && extra.is_none()) {
+            let accessors = self.generate_accessors(&name, &fields, extra.as_deref());
+            self.structs[index].1.push_str("\n\n");
+            self.structs[index].1.push_str(&accessors);
+        }
+        name
+    }
+
+    /// Generates an impl with a getter for every field of struct `name`, given as
+    /// `(name, type, is_optional)`, and for the `extra` catch-all if there is one.
+    fn generate_accessors(
+        &self,
+        name: &str,
+        fields: &[(String, String, bool)],
+        extra: Option<&str>,
+    ) -> String {
+        let indent = self.indent;
+        let mut methods = Vec::new();
+        for (field_name, ty, is_optional) in fields {
+            let is_copy = COPY_TYPES.contains(&ty.as_str());
+            let (ty, body) = match (is_optional, is_copy) {
+                (false, false) => (format!("&{}", ty), format!("&self.{}", field_name)),
+                (false, true) => (ty.clone(), format!("self.{}", field_name)),
+                (true, false) => (
+                    format!("Option<&{}>", ty),
+                    format!("self.{}.as_ref()", field_name),
+                ),
+                (true, true) => (format!("Option<{}>", ty), format!("self.{}", field_name)),
+            };
+            methods.push(format!(
+                "{i}pub fn {f}(&self) -> {t} {{\n{i}{i}{b}\n{i}}}\n",
+                i = indent,
+                f = field_name,
+                t = ty,
+                b = body
+            ));
+        }
+        if let Some(extra) = extra {
+            methods.push(format!(
+                "{i}pub fn {f}(&self) -> &HashMap<String, Value> {{\n{i}{i}&self.{f}\n{i}}}\n",
+                i = indent,
+                f = extra
+            ));
+        }
+        format!(
+            "{}impl {} {{\n{}}}",
+            allow_non_snake_case(fields),
+            name,
+            methods.join("\n")
+        )
+    }
+
+    /// Generates a struct for every distinct set of keys among the objects in `values`,
+    /// plus an untagged enum named `name` with a variant per struct. A single shape
+    /// generates a plain struct instead.
+    fn generate_shape_enum(&mut self, name: &str, values: &[&Value]) -> String {
+        let mut shapes: Vec<(Vec<&String>, Vec<&Value>)> = Vec::new();
+        for value in values {
+            let mut keys: Vec<&String> = match value {
+                Value::Object(map) => map.keys().collect(),
+                _ => continue,
+            };
+            keys.sort();
+            match shapes.iter_mut().find(|(shape, _)| *shape == keys) {
+                Some((_, objects)) => objects.push(value),
+                None => shapes.push((keys, vec![value])),
+            }
+        }
+        if shapes.len() < 2 {
+            return self.generate_struct(&self.options.type_name(name), values);
+        }
+        if self.options.rust.tag_style == TagStyle::Internal {
+            let keys: Vec<&str> = match &self.options.rust.tag_key {
+                Some(key) => vec![key.as_str()],
+                None => TAG_KEYS.to_vec(),
+            };
+            if let Some((key, groups)) = keys
+                .into_iter()
+                .find_map(|key| tag_groups(values, key).map(|groups| (key, groups)))
+            {
+                return self.generate_tagged_enum(name, key, &groups);
+            }
+        }
+        // Serde tries untagged variants in order and ignores unknown keys, so shapes
+        // with more keys have to come first or a smaller one would always match.
+        shapes.sort_by_key(|(keys, _)| Reverse(keys.len()));
+
+        let base = name;
+        let name = self.unique_struct_name(&self.options.type_name(base));
+        let index = self.structs.len();
+        self.structs.push((name.clone(), String::new()));
+
+        let visibility = if self.options.rust.public { "pub " } else { "" };
+        let mut output = String::new();
+        for (number, (_, objects)) in shapes.iter().enumerate() {
+            let variant = format!("Shape{}", number + 1);
+            let struct_name = self.options.type_name(&format!("{}{}", base, variant));
+            let ty = self.generate_struct(&struct_name, objects);
+            output.push_str(&format!("{}{}({}),\n", self.indent, variant, ty));
+        }
+        self.structs[index].1 = format!(
+            "{}#[serde(untagged)]\n{}enum {} {{\n{}}}",
+            self.derive_attribute(false),
+            visibility,
+            name,
+            output
+        );
+        name
+    }
+
+    /// Generates an enum tagged by `key`, with a variant per group of objects sharing
+    /// a value for it. The variants' structs leave out the key, which serde reads.
+    fn generate_tagged_enum(
+        &mut self,
+        name: &str,
+        key: &str,
+        groups: &[(&str, Vec<&Value>)],
+    ) -> String {
+        let base = name;
+        let name = self.unique_struct_name(&self.options.type_name(base));
+        let index = self.structs.len();
+        self.structs.push((name.clone(), String::new()));
+
+        let visibility = if self.options.rust.public { "pub " } else { "" };
+        let mut output = String::new();
+        let mut variants: Vec<String> = Vec::new();
+        for (tag, objects) in groups {
+            let mut variant = variant_name(tag);
+            while variants.contains(&variant) {
+                variant.push('_');
+            }
+            let objects: Vec<Value> = objects
+                .iter()
+                .filter_map(|value| value.as_object())
+                .map(|map| {
+                    Value::Object(
+                        map.iter()
+                            .filter(|(other, _)| *other != key)
+                            .map(|(other, value)| (other.clone(), value.clone()))
+                            .collect(),
+                    )
+                })
+                .collect();
+            let objects: Vec<&Value> = objects.iter().collect();
+            let struct_name = self.options.type_name(&format!("{}{}", base, variant));
+            let ty = self.generate_struct(&struct_name, &objects);
+            if variant != *tag {
+                output.push_str(&format!(
+                    "{}#[serde(rename = {})]\n",
+                    self.indent,
+                    Value::String(tag.to_string())
+                ));
+            }
+            output.push_str(&format!("{}{}({}),\n", self.indent, variant, ty));
+            variants.push(variant);
+        }
+        self.structs[index].1 = format!(
+            "{}#[serde(tag = {})]\n{}enum {} {{\n{}}}",
+            self.derive_attribute(false),
+            Value::String(key.to_string()),
+            visibility,
+            name,
+            output
+        );
+        name
+    }
+}
+
+/// Casing conventions serde can apply to every field of a struct with `rename_all`.
+const RENAME_ALL_RULES: [&str; 4] = [
+    "camelCase",
+    "PascalCase",
+    "kebab-case",
+    "SCREAMING_SNAKE_CASE",
+];
+
+/// Finds the `rename_all` rule that turns every field name into its key, given as
+/// `(key, field name)` pairs. Returns `None` when no field needs renaming or the keys
+/// don't follow a single convention.
+fn rename_all_rule<'a>(
+    fields: impl Iterator<Item = (&'a String, &'a String)> + Clone,
+) -> Option<&'static str> {
+    let fields = fields.map(|(key, name)| (key, name.trim_start_matches("r#")));
+    if fields.clone().all(|(key, name)| key == name) {
+        return None;
+    }
+    RENAME_ALL_RULES.into_iter().find(|rule| {
+        fields
+            .clone()
+            .all(|(key, name)| *key == apply_rename_rule(rule, name))
+    })
+}
+
+/// Renames a snake_case field the way serde's `rename_all = rule` does.
+fn apply_rename_rule(rule: &str, name: &str) -> String {
+    match rule {
+        "camelCase" | "PascalCase" => {
+            let mut renamed = String::new();
+            let mut capitalize = rule == "PascalCase";
+            for c in name.chars() {
+                if c == '_' {
+                    capitalize = true;
+                } else if capitalize {
+                    renamed.push(c.to_ascii_uppercase());
+                    capitalize = false;
+                } else {
+                    renamed.push(c);
+                }
+            }
+            renamed
+        }
+        "kebab-case" => name.replace('_', "-"),
+        _ => name.to_ascii_uppercase(),
+    }
+}
+
+/// Returns `#[allow(non_snake_case)]` when any of the fields, given as
+/// `(name, type, is_optional)`, kept a name with uppercase letters.
+fn allow_non_snake_case(fields: &[(String, String, bool)]) -> &'static str {
+    if fields
+        .iter()
+        .any(|(name, _, _)| name.chars().any(|c| c.is_uppercase()))
+    {
+        "#[allow(non_snake_case)]\n"
+    } else {
+        ""
+    }
+}
+
+/// Prints the root struct followed by a struct for every nested object. When the
+/// input holds no objects at all, e.g. `["a", "b"]`, the root is a type alias for the
+/// inferred `Vec` instead.
+pub fn print_rust_struct(values: &[Value], options: &Options) -> String {
+    let mut generator = RustGenerator::new(options);
+    let mut output = Vec::new();
+    let (root, sample) = if !values.is_empty() && !values.iter().any(|value| value.is_object()) {
+        let root = Value::Array(values.to_vec());
+        let visibility = if options.rust.public { "pub " } else { "" };
+        let name = options.type_name(&options.name);
+        let ty = generator.array_type(&options.name, &[&root]);
+        output.push(format!("{}type {} = {};", visibility, name, ty));
+        (name, Some(root))
+    } else {
+        let values: Vec<&Value> = values.iter().collect();
+        let name = if options.rust.split_shapes {
+            generator.generate_shape_enum(&options.name, &values)
+        } else {
+            generator.generate_struct(&options.type_name(&options.name), &values)
+        };
+        (name, values.first().map(|value| (*value).clone()))
+    };
+
+    output.extend(generator.structs.into_iter().map(|(_, body)| body));
+    if options.rust.imports {
+        let uses = imports(&output.join("\n"), &options.rust.derives);
+        if !uses.is_empty() {
+            output.insert(0, uses);
+        }
+    }
+    if generator.uses_numeric_strings {
+        output.push(numeric_string_functions(generator.indent));
+    }
+    if let Some(sample) = sample.filter(|_| options.rust.emit_tests) {
+        output.push(round_trip_test(&root, &sample, generator.indent));
+    }
+    let output = output.join(if options.compact.is_some() {
+        "\n"
+    } else {
+        "\n\n"
+    });
+    match &options.rust.module {
+        Some(module) => wrap_in_module(module, &output, generator.indent),
+        None => output,
+    }
+}
+
+/// Returns the `use` declarations `code` needs: `serde` for the `Serialize` and
+/// `Deserialize` derives, and `Value` and `HashMap` when they appear unqualified
+/// outside comments and string literals.
+fn imports(code: &str, derives: &[String]) -> String {
+    let identifiers = code_identifiers(code);
+    let serde: Vec<&str> = ["Deserialize", "Serialize"]
+        .into_iter()
+        .filter(|derive| derives.iter().any(|d| d == derive))
+        .collect();
+    let mut uses = Vec::new();
+    match serde.as_slice() {
+        [] => {}
+        [derive] => uses.push(format!("use serde::{};", derive)),
+        _ => uses.push(format!("use serde::{{{}}};", serde.join(", "))),
+    }
+    if identifiers.contains("Value") {
+        uses.push("use serde_json::Value;".to_string());
+    }
+    if identifiers.contains("HashMap") {
+        uses.push("use std::collections::HashMap;".to_string());
+    }
+    if identifiers.contains("Rc") {
+        uses.push("use std::rc::Rc;".to_string());
+    }
+    if identifiers.contains("Arc") {
+        uses.push("use std::sync::Arc;".to_string());
+    }
+    uses.join("\n")
+}
+
+/// Collects the paths, such as `Value` or `serde_json::Value`, used in `code`.
+fn code_identifiers(code: &str) -> HashSet<String> {
+    let mut identifiers = HashSet::new();
+    for line in code
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+    {
+        let mut current = String::new();
+        let mut in_string = false;
+        let mut escaped = false;
+        for c in line.chars() {
+            if in_string {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => in_string = false,
+                    _ => {}
+                }
+            } else if c.is_alphanumeric() || c == '_' || c == ':' {
+                current.push(c);
+                continue;
+            } else if c == '"' {
+                in_string = true;
+            }
+            if !current.is_empty() {
+                identifiers.insert(std::mem::take(&mut current));
+            }
+        }
+        if !current.is_empty() {
+            identifiers.insert(current);
+        }
+    }
+    identifiers
+}
+
+/// Writes the `deserialize_with` functions for fields holding numbers as strings.
+fn numeric_string_functions(indent: &str) -> String {
+    let bounds = format!(
+        "where\n{i}D: serde::Deserializer<'de>,\n{i}T: std::str::FromStr,\n{i}T::Err: std::fmt::Display,\n",
+        i = indent
+    );
+    format!(
+        "/// Parses a number sent as a string, such as `\"30\"`.\nfn from_numeric_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>\n{b}{{\n{i}let s = <String as serde::Deserialize>::deserialize(deserializer)?;\n{i}s.parse().map_err(serde::de::Error::custom)\n}}\n\n/// Parses a number sent as a string, or `null`.\nfn from_optional_numeric_string<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>\n{b}{{\n{i}let s = <Option<String> as serde::Deserialize>::deserialize(deserializer)?;\n{i}s.map(|s| s.parse().map_err(serde::de::Error::custom))\n{i}{i}.transpose()\n}}",
+        b = bounds,
+        i = indent
+    )
+}
+
+/// Writes a `#[cfg(test)]` module with a test deserializing `sample`, embedded as a
+/// raw string literal, into the root type.
+fn round_trip_test(root: &str, sample: &Value, indent: &str) -> String {
+    let json = sample.to_string();
+    let mut hashes = "#".to_string();
+    while json.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+    let indent2 = indent.repeat(2);
+    format!(
+        "#[cfg(test)]\nmod tests {{\n{i}use super::*;\n\n{i}#[test]\n{i}fn deserializes_sample() {{\n{i2}let sample = r{h}\"{json}\"{h};\n{i2}let parsed: Result<{root}, _> = serde_json::from_str(sample);\n{i2}assert!(parsed.is_ok(), \"{{:?}}\", parsed.err());\n{i}}}\n}}",
+        i = indent,
+        i2 = indent2,
+        h = hashes,
+        json = json,
+        root = root
+    )
+}
+
+/// Wraps the generated code in `pub mod module { ... }`, indenting every line by one
+/// level.
+fn wrap_in_module(module: &str, code: &str, indent: &str) -> String {
+    let body: Vec<String> = code
+        .lines()
+        .map(|line| match line {
+            "" => String::new(),
+            _ => format!("{}{}", indent, line),
+        })
+        .collect();
+    format!("pub mod {} {{\n{}\n}}", module, body.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_output;
+    use serde_json::json;
+
+    fn with_rust(rust: &RustOptions) -> Options {
+        Options {
+            rust: rust.clone(),
+            ..Options::default()
+        }
+    }
+
+    #[test]
+    fn test_print_rust_struct() {
+        let json_value = json!([
+            {
+                "name": "Alice",
+                "age": 30.0,
+                "is_student": false
+            },
+            {
+                "name": "Bob",
+                "age": 25.0,
+                "is_student": true,
+                "address": null
+            }
+        ]);
+
+        let expected_output =
+            "struct Data {\n// Always null in the input, so the real type is unknown.\naddress: Option<serde_json::Value>;\nage: f64;\nis_student: bool;\nname: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_partial_fields() {
+        let json_value = json!([
+            {
+                "name": "Alice",
+                "age": 30.0
+            },
+            {
+                "name": "Bob",
+                "is_student": true
+            }
+        ]);
+
+        let expected_output =
+            "struct Data {\nage: Option<f64>;\nis_student: Option<bool>;\nname: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_optionality() {
+        let json_value = json!([{ "name": "Alice", "nickname": null }]);
+        let expected_output = "struct Data {\nname: String;\n// Always null in the input, so the real type is unknown.\nnickname: Option<serde_json::Value>;\n}";
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+
+        let json_value = json!([{ "age": null }, { "age": 30 }]);
+        let expected_output = "struct Data {\nage: Option<i64>;\n}";
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_nested_objects() {
+        let json_value = json!([
+            {
+                "name": "Alice",
+                "shipping_address": { "city": "Paris" },
+                "orders": [{ "id": 1 }, { "id": 2, "total": 9.5 }]
+            },
+            {
+                "name": "Bob",
+                "shipping_address": { "city": "Oslo", "zip": "0150" },
+                "orders": []
+            }
+        ]);
+
+        let expected_output = "struct Data {\nname: String;\norders: Vec<Order>;\nshipping_address: ShippingAddress;\n}\n\nstruct Order {\nid: i64;\ntotal: Option<f64>;\n}\n\nstruct ShippingAddress {\ncity: String;\nzip: Option<String>;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_identical_shapes() {
+        let json_value = json!([{
+            "billing": { "city": "Paris", "geo": { "lat": 1.5 } },
+            "shipping": { "city": "Oslo", "geo": { "lat": 2.5 } },
+            "home": { "city": "Rome" }
+        }]);
+
+        let expected_output = "struct Data {\nbilling: Billing;\nhome: Home;\nshipping: Billing;\n}\n\nstruct Billing {\ncity: String;\ngeo: Geo;\n}\n\nstruct Geo {\nlat: f64;\n}\n\nstruct Home {\ncity: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_array_elements() {
+        let json_value = json!([
+            { "tags": ["a", "b"], "scores": [1, 2], "mixed": [1, "two"], "empty": [] },
+            { "tags": ["c"], "scores": [], "mixed": [], "empty": [] }
+        ]);
+
+        let expected_output = "struct Data {\nempty: Vec<Value>;\nmixed: Vec<Value>;\nscores: Vec<i64>;\ntags: Vec<String>;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_nested_arrays() {
+        let json_value = json!([
+            { "matrix": [[1, 2], [3, 4]], "cube": [[[0.5]], [[1, 2], []]], "points": [[{ "x": 1 }]] },
+            { "matrix": [[5]], "cube": [], "points": [] }
+        ]);
+
+        let expected_output = "struct Data {\ncube: Vec<Vec<Vec<f64>>>;\nmatrix: Vec<Vec<i64>>;\npoints: Vec<Vec<Point>>;\n}\n\nstruct Point {\nx: i64;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_primitive_array() {
+        let json_value = json!(["a", "b", "c"]);
+        let output = print_rust_struct(json_value.as_array().unwrap(), &Options::default());
+        assert_eq!(output, "type Data = Vec<String>;");
+
+        let json_value = json!([1, "two", null]);
+        let output = print_rust_struct(json_value.as_array().unwrap(), &Options::default());
+        assert_eq!(output, "type Data = Vec<Value>;");
+    }
+
+    #[test]
+    fn test_print_rust_struct_map_threshold() {
+        let json_value = json!([
+            {
+                "labels": { "en": "Hello", "fr": "Bonjour" },
+                "scores": { "alice": 1, "bob": null, "carol": 2.5 },
+                "point": { "x": 1, "y": 2, "z": "three" }
+            },
+            { "labels": { "de": "Hallo" }, "scores": {}, "point": { "x": 3, "y": 4, "z": "four" } }
+        ]);
+        let options = RustOptions {
+            map_threshold: Some(3),
+            ..RustOptions::default()
+        };
+
+        let expected_output = "struct Data {\nlabels: HashMap<String, String>;\npoint: Point;\nscores: HashMap<String, Option<f64>>;\n}\n\nstruct Point {\nx: i64;\ny: i64;\nz: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_derives() {
+        let json_value = json!([{ "name": "Alice", "address": { "city": "Paris" } }]);
+
+        let mut options = RustOptions::default();
+        options.set_derives("serde");
+        let output = print_rust_struct(json_value.as_array().unwrap(), &with_rust(&options));
+        assert_eq!(
+            output
+                .matches("#[derive(Debug, Clone, Serialize, Deserialize)]\nstruct ")
+                .count(),
+            2
+        );
+
+        options.set_derives("Debug, PartialEq");
+        let output = print_rust_struct(json_value.as_array().unwrap(), &with_rust(&options));
+        assert!(output.starts_with("#[derive(Debug, PartialEq)]\nstruct Data {"));
+    }
+
+    #[test]
+    fn test_print_rust_struct_public() {
+        let json_value = json!([{ "name": "Alice", "address": { "city": "Paris" } }]);
+        let options = RustOptions {
+            public: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output = "pub struct Data {\npub address: Address;\npub name: String;\n}\n\npub struct Address {\npub city: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_renamed_fields() {
+        let json_value = json!([{ "firstName": "Alice", "age": 30 }]);
+
+        let expected_output =
+            "struct Data {\nage: i64;\n#[serde(rename = \"firstName\")]\nfirst_name: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+
+        let json_value = json!([{ "say \"hi\"": "hello" }]);
+        assert_eq!(
+            format_output(&print_rust_struct(
+                json_value.as_array().unwrap(),
+                &Options::default()
+            )),
+            "struct Data {\n#[serde(rename = \"say \\\"hi\\\"\")]\nsay_hi: String;\n}"
+        );
+    }
+
+    #[test]
+    fn test_print_rust_struct_colliding_field_names() {
+        let json_value =
+            json!([{ "firstName": "Alice", "first_name": "Bob", "first-name": "Carol" }]);
+
+        let expected_output = "struct Data {\n#[serde(rename = \"first-name\")]\nfirst_name_2: String;\n#[serde(rename = \"firstName\")]\nfirst_name_3: String;\nfirst_name: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_keep_original_names() {
+        let json_value = json!([{ "firstName": "Alice", "created-at": "today", "age": 30 }]);
+        let options = RustOptions {
+            keep_names: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output = "#[allow(non_snake_case)]\nstruct Data {\nage: i64;\n#[serde(rename = \"created-at\")]\ncreated_at: String;\nfirstName: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_keywords() {
+        let json_value = json!([{ "type": "admin", "self": "/users/1" }]);
+        let expected_output =
+            "struct Data {\n#[serde(rename = \"self\")]\nself_: String;\nr#type: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_numeric_promotion() {
+        let json_value = json!([
+            { "age": 30, "score": 10, "ids": [1, 2] },
+            { "age": 25.5, "score": 18446744073709551615u64, "ids": [3.5] }
+        ]);
+
+        let expected_output = "struct Data {\nage: f64;\nids: Vec<f64>;\nscore: i64;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_decimal() {
+        let json_values: Vec<Value> = serde_json::from_str(
+            r#"[{"id": 1, "balance": 12.5, "order": {"total": 123456789012345678901234}}]"#,
+        )
+        .unwrap();
+
+        let warnings = precision_warnings(&json_values, &[], &Options::default());
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("field `order.total` holds 1.2345678901234567e23"));
+
+        let options = RustOptions {
+            decimal: true,
+            ..RustOptions::default()
+        };
+        let actual_output = format_output(&print_rust_struct(&json_values, &with_rust(&options)));
+        assert!(actual_output.ends_with("struct Order {\ntotal: rust_decimal::Decimal;\n}"));
+    }
+
+    #[test]
+    fn test_precision_warnings_inexact_floats() {
+        let json = r#"[{"price": 0.1, "rate": 0.12345678901234567890, "big": 1e2, "id": 123456789012345678901, "tags": ["\"0.12345678901234567890"]}]"#;
+        let inexact = inexact_numbers(json);
+        assert_eq!(inexact, ["0.12345678901234567890", "123456789012345678901"]);
+
+        let json_values: Vec<Value> = serde_json::from_str(json).unwrap();
+        let warnings = precision_warnings(&json_values, &inexact, &Options::default());
+        assert_eq!(warnings.len(), 2);
+        assert!(
+            warnings[0].starts_with("field `id` holds 123456789012345678901, which doesn't fit")
+        );
+        assert!(warnings[1].starts_with(
+            "field `rate` holds 0.12345678901234567890, which reads as 0.12345678901234568 in an f64"
+        ));
+
+        let options = with_rust(&RustOptions {
+            decimal: true,
+            ..RustOptions::default()
+        });
+        assert_eq!(
+            precision_warnings(&json_values, &inexact, &options).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_print_rust_struct_conflicting_types() {
+        let json_value = json!([{ "id": "a1", "name": "Alice" }, { "id": 7, "name": "Bob" }]);
+
+        let expected_output =
+            "struct Data {\n// observed: Number, String\nid: Value;\nname: String;\n}";
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+
+        let options = RustOptions {
+            conflict_enums: true,
+            ..RustOptions::default()
+        };
+        let expected_output = "struct Data {\nid: IdField;\nname: String;\n}\n\n#[serde(untagged)]\nenum IdField {\nNumber(i64);\nString(String);\n}";
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_chrono() {
+        let json_value = json!([
+            { "created_at": "2023-01-02T15:04:05Z", "birthday": "1990-05-17", "note": "2023-01-02T15:04:05Z" },
+            { "created_at": "2023-02-03T10:00:00+02:00", "birthday": "2001-12-31", "note": "tomorrow" }
+        ]);
+        let options = RustOptions {
+            chrono: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output = "struct Data {\nbirthday: chrono::NaiveDate;\ncreated_at: chrono::DateTime<chrono::Utc>;\nnote: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_smart_strings() {
+        let json_value = json!([
+            { "homepage": "https://example.com", "contact": "mailto:a@example.com", "note": "see: below" },
+            { "homepage": "http://example.org/a?b=c", "contact": "a@example.com", "note": "todo:1" }
+        ]);
+        let options = RustOptions {
+            smart_strings: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output =
+            "struct Data {\ncontact: String;\nhomepage: url::Url;\nnote: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_split_shapes() {
+        let json_value = json!([
+            { "type": "click", "x": 1, "y": 2 },
+            { "type": "key", "key": "a" },
+            { "type": "click", "x": 3, "y": 4 }
+        ]);
+        let options = RustOptions {
+            split_shapes: true,
+            tag_style: TagStyle::Untagged,
+            ..RustOptions::default()
+        };
+
+        let expected_output = "#[serde(untagged)]\nenum Data {\nShape1(DataShape1);\nShape2(DataShape2);\n}\n\nstruct DataShape1 {\nr#type: String;\nx: i64;\ny: i64;\n}\n\nstruct DataShape2 {\nkey: String;\nr#type: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_split_shapes_tagged() {
+        let json_value = json!([
+            { "kind": "circle", "radius": 1.5 },
+            { "kind": "rect", "width": 2, "height": 3 },
+            { "kind": "circle", "radius": 2.0 },
+            { "kind": "rect", "width": 4, "height": 5, "label": "box" }
+        ]);
+        let options = RustOptions {
+            split_shapes: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output = "#[serde(tag = \"kind\")]\nenum Data {\n#[serde(rename = \"circle\")]\nCircle(DataCircle);\n#[serde(rename = \"rect\")]\nRect(DataRect);\n}\n\nstruct DataCircle {\nradius: f64;\n}\n\nstruct DataRect {\nheight: i64;\nlabel: Option<String>;\nwidth: i64;\n}";
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+
+        let json_value = json!([{ "kind": "say \"hi\"", "a": 1 }, { "kind": "bye", "b": 2 }]);
+        let expected_output = "#[serde(tag = \"kind\")]\nenum Data {\n#[serde(rename = \"say \\\"hi\\\"\")]\nSayHi(DataSayHi);\n#[serde(rename = \"bye\")]\nBye(DataBye);\n}\n\nstruct DataSayHi {\na: i64;\n}\n\nstruct DataBye {\nb: i64;\n}";
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+
+        let options = RustOptions {
+            split_shapes: true,
+            tag_key: Some("radius".to_string()),
+            ..RustOptions::default()
+        };
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert!(actual_output.starts_with("#[serde(untagged)]"));
+    }
+
+    #[test]
+    fn test_print_rust_struct_non_exhaustive() {
+        let json_value = json!([{ "name": "Alice", "address": { "city": "Paris" } }]);
+        let mut options = RustOptions {
+            public: true,
+            non_exhaustive: true,
+            ..RustOptions::default()
+        };
+        options.set_derives("Debug");
+
+        let expected_output = "#[derive(Debug)]\n#[non_exhaustive]\npub struct Data {\npub address: Address;\npub name: String;\n}\n\n#[derive(Debug)]\n#[non_exhaustive]\npub struct Address {\npub city: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_recursive() {
+        let json_value = json!([{
+            "name": "root",
+            "children": [{ "name": "a", "children": [], "parent": null }],
+            "parent": { "name": "up", "children": [], "parent": null }
+        }]);
+
+        let expected_output =
+            "struct Data {\nchildren: Vec<Data>;\nname: String;\nparent: Option<Box<Data>>;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_default() {
+        let json_value = json!([
+            { "status": "active", "tags": ["a"], "address": { "zip": 75001 } },
+            { "status": "closed", "tags": [] }
+        ]);
+        let mut options = RustOptions {
+            default: true,
+            infer_enums: Some(5),
+            ..RustOptions::default()
+        };
+        options.set_derives("Debug");
+
+        let expected_output = "#[derive(Debug)]\nstruct Data {\naddress: Option<Address>;\nstatus: Status;\ntags: Vec<String>;\n}\n\nimpl Default for Data {\nfn default() -> Self {\nData {\naddress: Default::default();\nstatus: Status::Active;\ntags: Default::default();\n}\n}\n}\n\n#[derive(Debug, Default)]\nstruct Address {\nzip: i64;\n}\n\n#[derive(Debug)]\nenum Status {\n#[serde(rename = \"active\")]\nActive;\n#[serde(rename = \"closed\")]\nClosed;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_observed_conflicts() {
+        let json_value = json!([
+            { "id": 1, "payload": { "a": 1 } },
+            { "id": "2", "payload": [1] },
+            { "id": null, "payload": {} }
+        ]);
+
+        let expected_output = "struct Data {\n// observed: Number, String\nid: Option<Value>;\n// observed: Array, Object\npayload: Value;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_name_affixes() {
+        let json_value = json!([{ "address": { "city": "Paris" } }]);
+        let options = Options {
+            name_prefix: "Api".to_string(),
+            name_suffix: "Dto".to_string(),
+            ..Options::default()
+        };
+
+        let expected_output = "struct ApiDataDto {\naddress: ApiAddressDto;\n}\n\nstruct ApiAddressDto {\ncity: String;\n}";
+
+        let actual_output =
+            format_output(&print_rust_struct(json_value.as_array().unwrap(), &options));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_module() {
+        let json_value = json!([{ "address": { "city": "Paris" } }]);
+        let options = RustOptions {
+            module: Some("models".to_string()),
+            ..RustOptions::default()
+        };
+
+        let expected_output = "pub mod models {\n    struct Data {\n        address: Address,\n    }\n\n    struct Address {\n        city: String,\n    }\n}";
+
+        let actual_output = print_rust_struct(json_value.as_array().unwrap(), &with_rust(&options));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_decode_base64() {
+        let json_value = json!([
+            { "data": "SGVsbG8sIHdvcmxkIQ==", "thumb": "iVBORw0KGgoAAAANSUhEUg==", "word": "abcdefghijklmnop" },
+            { "data": "AAECAwQFBgcICQoLDA0ODw==", "thumb": null, "word": "ABCDEFGHIJKLMNOP" }
+        ]);
+        let options = RustOptions {
+            decode_base64: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output = "struct Data {\n#[serde(with = \"base64\")]\ndata: Vec<u8>;\n#[serde(with = \"base64::option\")]\nthumb: Option<Vec<u8>>;\nword: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+        assert!(!is_base64("SGVsbG8=SGVsbG8="));
+        assert!(!is_base64("c29tZQ=="));
+    }
+
+    #[test]
+    fn test_print_rust_struct_null_policy() {
+        let json_value = json!([
+            { "name": "Alice", "age": 30, "email": "a@example.com" },
+            { "name": null, "email": null },
+            { "name": "Carol" }
+        ]);
+        let output = |null_policy| {
+            let options = RustOptions {
+                null_policy,
+                ..RustOptions::default()
+            };
+            format_output(&print_rust_struct(
+                json_value.as_array().unwrap(),
+                &with_rust(&options),
+            ))
+        };
+
+        assert_eq!(
+            output(NullPolicy::Optional),
+            "struct Data {\nage: Option<i64>;\nemail: Option<String>;\nname: Option<String>;\n}"
+        );
+        assert_eq!(
+            output(NullPolicy::Value),
+            "struct Data {\nage: Option<i64>;\nemail: Option<String>;\nname: String;\n}"
+        );
+        assert_eq!(
+            output(NullPolicy::Strict),
+            "struct Data {\nage: Option<i64>;\n#[serde(default, with = \"::serde_with::rust::double_option\")]\nemail: Option<Option<String>>;\nname: Option<String>;\n}"
+        );
+        assert_eq!("strict".parse(), Ok(NullPolicy::Strict));
+    }
+
+    #[test]
+    fn test_print_rust_struct_try_from() {
+        let json_value = json!([
+            { "name": "Alice", "tags": ["a"], "address": { "zip": 75001 } },
+            { "name": "Bob", "tags": [] }
+        ]);
+        let options = RustOptions {
+            try_from: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output = "struct Data {\naddress: Option<Address>;\nname: String;\ntags: Vec<String>;\n}\n\nimpl TryFrom<&Value> for Data {\ntype Error = String;\n\nfn try_from(value: &Value) -> Result<Self, Self::Error> {\nlet object = value.as_object().ok_or(\"expected an object\")?;\nOk(Data {\naddress: object.get(\"address\").filter(|v| !v.is_null()).and_then(|v| Address::try_from(v).ok());\nname: object.get(\"name\").and_then(|v| v.as_str().map(str::to_string)).ok_or(\"missing or invalid field name\")?;\ntags: object.get(\"tags\").and_then(|v| v.as_array().and_then(|items| items.iter().map(|item| item.as_str().map(str::to_string)).collect::<Option<Vec<_>>>())).ok_or(\"missing or invalid field tags\")?;\n})\n}\n}\n\nstruct Address {\nzip: i64;\n}\n\nimpl TryFrom<&Value> for Address {\ntype Error = String;\n\nfn try_from(value: &Value) -> Result<Self, Self::Error> {\nlet object = value.as_object().ok_or(\"expected an object\")?;\nOk(Address {\nzip: object.get(\"zip\").and_then(|v| v.as_i64()).ok_or(\"missing or invalid field zip\")?;\n})\n}\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_max_depth() {
+        let json_value = json!([{ "a": { "b": { "c": 1 } }, "list": [{ "d": {} }] }]);
+        let options = Options {
+            max_depth: Some(1),
+            ..Options::default()
+        };
+
+        let expected_output = "struct Data {\na: A;\nlist: Vec<List>;\n}\n\nstruct A {\nb: Value;\n}\n\nstruct List {\nd: Value;\n}";
+
+        let actual_output =
+            format_output(&print_rust_struct(json_value.as_array().unwrap(), &options));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_emit_tests() {
+        let json_value = json!([{ "quote": "say \"#hi\"" }, { "quote": "bye" }]);
+        let options = RustOptions {
+            emit_tests: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output = "struct Data {\nquote: String;\n}\n\n#[cfg(test)]\nmod tests {\nuse super::*;\n\n#[test]\nfn deserializes_sample() {\nlet sample = r##\"{\"quote\":\"say \\\"#hi\\\"\"}\"##;\nlet parsed: Result<Data, _> = serde_json::from_str(sample);\nassert!(parsed.is_ok(), \"{:?}\", parsed.err());\n}\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_rename_all() {
+        let options = with_rust(&RustOptions {
+            rename_all: true,
+            ..RustOptions::default()
+        });
+        let json_value = json!([{ "firstName": "Alice", "lastLoginAt": "x", "age": 30 }]);
+        let expected_output = "#[serde(rename_all = \"camelCase\")]\nstruct Data {\nage: i64;\nfirst_name: String;\nlast_login_at: String;\n}";
+        assert_eq!(
+            format_output(&print_rust_struct(json_value.as_array().unwrap(), &options)),
+            expected_output
+        );
+
+        let json_value = json!([{ "firstName": "Alice", "last-name": "Smith" }]);
+        let expected_output = "struct Data {\n#[serde(rename = \"firstName\")]\nfirst_name: String;\n#[serde(rename = \"last-name\")]\nlast_name: String;\n}";
+        assert_eq!(
+            format_output(&print_rust_struct(json_value.as_array().unwrap(), &options)),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_rust_struct_optional_arrays() {
+        // Each field combines an array of strings with being absent, null or empty.
+        let json_value = json!([
+            { "absent": ["x"], "null": ["x"], "empty": ["x"], "mixed": ["x"], "unknown": [] },
+            { "null": null, "empty": [], "mixed": null },
+            { "absent": ["y"], "null": ["y"], "empty": ["y"] }
+        ]);
+
+        let expected_output = "struct Data {\nabsent: Option<Vec<String>>;\nempty: Vec<String>;\nmixed: Option<Vec<String>>;\nnull: Option<Vec<String>>;\nunknown: Option<Vec<Value>>;\n}";
+        assert_eq!(
+            format_output(&print_rust_struct(
+                json_value.as_array().unwrap(),
+                &Options::default()
+            )),
+            expected_output
+        );
+
+        let options = with_rust(&RustOptions {
+            null_policy: NullPolicy::Strict,
+            ..RustOptions::default()
+        });
+        let expected_output = "struct Data {\nabsent: Option<Vec<String>>;\nempty: Vec<String>;\n#[serde(default, with = \"::serde_with::rust::double_option\")]\nmixed: Option<Option<Vec<String>>>;\nnull: Option<Vec<String>>;\nunknown: Option<Vec<Value>>;\n}";
+        assert_eq!(
+            format_output(&print_rust_struct(json_value.as_array().unwrap(), &options)),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_rust_struct_all_required() {
+        let json_value = json!([
+            { "name": "Alice", "age": null, "address": { "city": "Paris" } },
+            { "name": "Bob", "address": { "city": null, "zip": "0150" } }
+        ]);
+        let options = Options {
+            all_required: true,
+            ..Options::default()
+        };
+
+        let expected_output = "struct Data {\naddress: Address;\n// Always null in the input, so the real type is unknown.\nage: serde_json::Value;\nname: String;\n}\n\nstruct Address {\ncity: String;\nzip: String;\n}";
+        assert_eq!(
+            format_output(&print_rust_struct(json_value.as_array().unwrap(), &options)),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_rust_struct_deny_unknown() {
+        let json_value = json!([{ "name": "Alice", "address": { "city": "Paris" } }]);
+        let options = with_rust(&RustOptions {
+            deny_unknown: true,
+            ..RustOptions::default()
+        });
+
+        let expected_output = "#[serde(deny_unknown_fields)]\nstruct Data {\naddress: Address;\nname: String;\n}\n\n#[serde(deny_unknown_fields)]\nstruct Address {\ncity: String;\n}";
+        assert_eq!(
+            format_output(&print_rust_struct(json_value.as_array().unwrap(), &options)),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_rust_struct_imports() {
+        let mut rust = RustOptions {
+            imports: true,
+            extra: true,
+            ..RustOptions::default()
+        };
+        rust.set_derives("serde");
+        let json_value = json!([{ "Value": null, "meta": [1, "a"] }]);
+
+        let expected_output = "use serde::{Deserialize, Serialize};\nuse serde_json::Value;\nuse std::collections::HashMap;\n\n#[derive(Debug, Clone, Serialize, Deserialize)]\nstruct Data {\n// Always null in the input, so the real type is unknown.\n#[serde(rename = \"Value\")]\nvalue: Option<serde_json::Value>;\nmeta: Vec<Value>;\n#[serde(flatten)]\nextra: HashMap<String, Value>;\n}";
+        assert_eq!(
+            format_output(&print_rust_struct(
+                json_value.as_array().unwrap(),
+                &with_rust(&rust)
+            )),
+            expected_output
+        );
+
+        let json_value = json!([{ "Value": null }]);
+        let rust = RustOptions {
+            imports: true,
+            ..RustOptions::default()
+        };
+        let output = print_rust_struct(json_value.as_array().unwrap(), &with_rust(&rust));
+        assert!(output.starts_with("struct Data {"));
+    }
+
+    #[test]
+    fn test_print_rust_struct_coerce_numeric_strings() {
+        let json_value = json!([
+            { "age": "30", "score": "1.5", "zip": "0150", "code": "7" },
+            { "age": "41", "score": "2", "zip": "01 50", "code": null }
+        ]);
+        let options = with_rust(&RustOptions {
+            coerce_numeric_strings: true,
+            ..RustOptions::default()
+        });
+
+        let output = print_rust_struct(json_value.as_array().unwrap(), &options);
+        let structs = format_output(output.split("\n\n///").next().unwrap());
+        let expected_output = "struct Data {\n#[serde(deserialize_with = \"from_numeric_string\")]\nage: i64;\n#[serde(default, deserialize_with = \"from_optional_numeric_string\")]\ncode: Option<i64>;\n#[serde(deserialize_with = \"from_numeric_string\")]\nscore: f64;\nzip: String;\n}";
+        assert_eq!(structs, expected_output);
+        assert!(output.contains("fn from_numeric_string<'de, D, T>"));
+        assert!(output.contains("fn from_optional_numeric_string<'de, D, T>"));
+        assert!(!is_numeric_string(" 1"));
+        assert!(!is_numeric_string("NaN"));
+    }
+
+    #[test]
+    fn test_print_rust_struct_resolutions() {
+        let json_value = json!([
+            { "code": 7, "scores": { "a": 1, "b": 2 }, "status": "open" },
+            { "code": "x", "scores": { "c": 3 }, "status": "closed" }
+        ]);
+        let options = with_rust(&RustOptions {
+            resolutions: vec![
+                ("code".to_string(), Resolution::UntaggedEnum),
+                ("scores".to_string(), Resolution::HashMap),
+                ("status".to_string(), Resolution::Enum),
+            ],
+            ..RustOptions::default()
+        });
+
+        let expected_output = "struct Data {\ncode: CodeField;\nscores: HashMap<String, i64>;\nstatus: Status;\n}\n\n#[serde(untagged)]\nenum CodeField {\nNumber(i64);\nString(String);\n}\n\nenum Status {\n#[serde(rename = \"closed\")]\nClosed;\n#[serde(rename = \"open\")]\nOpen;\n}";
+        assert_eq!(
+            format_output(&print_rust_struct(json_value.as_array().unwrap(), &options)),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_rust_struct_newtypes() {
+        let json_value = json!([{
+            "user_id": 7,
+            "orderId": "A-1",
+            "id": 3,
+            "owner": { "user_id": 8 },
+            "paid": true
+        }]);
+        let options = with_rust(&RustOptions {
+            newtype_suffixes: vec!["_id".to_string(), "Id".to_string()],
+            ..RustOptions::default()
+        });
+
+        let expected_output = "struct Data {\nid: i64;\n#[serde(rename = \"orderId\")]\norder_id: OrderId;\nowner: Owner;\npaid: bool;\nuser_id: UserId;\n}\n\nstruct OrderId(String);\n\nstruct Owner {\nuser_id: UserId;\n}\n\nstruct UserId(i64);";
+        assert_eq!(
+            format_output(&print_rust_struct(json_value.as_array().unwrap(), &options)),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_rust_struct_flat() {
+        let json_value =
+            json!([{ "name": "Alice", "address": { "city": "Paris" }, "tags": ["a"] }]);
+        let options = with_rust(&RustOptions {
+            flat: true,
+            ..RustOptions::default()
+        });
+
+        let expected_output =
+            "struct Data {\naddress: HashMap<String, Value>;\nname: String;\ntags: Vec<Value>;\n}";
+        assert_eq!(
+            format_output(&print_rust_struct(json_value.as_array().unwrap(), &options)),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_rust_struct_type_overrides() {
+        let json_value = json!([
+            { "price": 9.5, "created_at": "2024-01-01T00:00:00Z", "items": [{ "price": 1 }] },
+            { "price": null, "created_at": "2024-01-02T00:00:00Z", "items": [] }
+        ]);
+        let mut rust = RustOptions::default();
+        rust.set_type_overrides("price=Decimal, created_at=DateTime<Utc>,")
+            .unwrap();
+        let options = with_rust(&rust);
+
+        let expected_output = "struct Data {\ncreated_at: DateTime<Utc>;\nitems: Vec<Item>;\nprice: Option<Decimal>;\n}\n\nstruct Item {\nprice: Decimal;\n}";
+        assert_eq!(
+            format_output(&print_rust_struct(json_value.as_array().unwrap(), &options)),
+            expected_output
+        );
+
+        let mut rust = RustOptions::default();
+        rust.set_type_overrides("counts=HashMap<String, i64>")
+            .unwrap();
+        assert_eq!(
+            rust.type_overrides,
+            [("counts".to_string(), "HashMap<String, i64>".to_string())]
+        );
+        assert!(rust.set_type_overrides("price").is_err());
+    }
+
+    #[test]
+    fn test_print_rust_struct_trace_optional() {
+        let json_value = json!([
+            { "id": 1, "email": "a@b.c", "nickname": "al" },
+            { "id": 2, "email": null, "nickname": "bo" },
+            { "id": 3, "nickname": null },
+            { "id": 4, "address": { "city": "Paris" } },
+            { "id": 5, "address": { "zip": "75001" } }
+        ]);
+        let options = with_rust(&RustOptions {
+            trace_optional: true,
+            ..RustOptions::default()
+        });
+
+        let expected_output = "struct Data {\n// optional: first missing from object 0\naddress: Option<Address>;\n// optional: first missing from object 2, first null in object 1\nemail: Option<String>;\nid: i64;\n// optional: first missing from object 3, first null in object 2\nnickname: Option<String>;\n}\n\nstruct Address {\n// optional: first missing from object 1\ncity: Option<String>;\n// optional: first missing from object 0\nzip: Option<String>;\n}";
+        assert_eq!(
+            format_output(&print_rust_struct(json_value.as_array().unwrap(), &options)),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_rust_struct_compact() {
+        let json_value = json!([{ "name": "Alice", "address": { "city": "Paris" } }]);
+        let options = Options {
+            compact: Some(4),
+            ..Options::default()
+        };
+
+        let expected_output = "struct Data {\n    address: Address,\n    name: String,\n}\nstruct Address {\n    city: String,\n}";
+        assert_eq!(
+            print_rust_struct(json_value.as_array().unwrap(), &options),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_rust_struct_shared() {
+        let json_value = json!([{
+            "name": "root",
+            "owner": { "id": 1 },
+            "tags": [{ "label": "a" }],
+            "parent": { "name": "p", "owner": { "id": 2 }, "tags": [{ "label": "b" }], "parent": null }
+        }]);
+        let options = with_rust(&RustOptions {
+            shared: Some(SharedPointer::Arc),
+            ..RustOptions::default()
+        });
+
+        let expected_output = "struct Data {\nname: String;\nowner: Arc<Owner>;\nparent: Option<Arc<Data>>;\ntags: Vec<Arc<Tag>>;\n}\n\nstruct Owner {\nid: i64;\n}\n\nstruct Tag {\nlabel: String;\n}";
+        assert_eq!(
+            format_output(&print_rust_struct(json_value.as_array().unwrap(), &options)),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_rust_struct_reserved_names() {
+        let json_value = json!([{
+            "value": { "amount": 1 },
+            "string": { "text": "a" },
+            "self": { "id": 2 },
+            "note": null
+        }]);
+
+        let expected_output = "struct Data {\n// Always null in the input, so the real type is unknown.\nnote: Option<serde_json::Value>;\n#[serde(rename = \"self\")]\nself_: Self2;\nstring: String2;\nvalue: Value2;\n}\n\nstruct Self2 {\nid: i64;\n}\n\nstruct String2 {\ntext: String;\n}\n\nstruct Value2 {\namount: i64;\n}";
+        assert_eq!(
+            format_output(&print_rust_struct(
+                json_value.as_array().unwrap(),
+                &Options::default()
+            )),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_is_uuid() {
+        assert!(is_uuid("67e55044-10b1-426f-9247-bb680e5fe0c8"));
+        assert!(is_uuid("67E55044-10B1-426F-9247-BB680E5FE0C8"));
+        assert!(!is_uuid("67e55044-10b1-426f-9247-bb680e5fe0c"));
+        assert!(!is_uuid("67e5504410b1426f9247bb680e5fe0c8"));
+        assert!(!is_uuid("g7e55044-10b1-426f-9247-bb680e5fe0c8"));
+        assert!(!is_uuid("67e55044-10b1-426f-9247bb68-0e5fe0c8"));
+    }
+
+    #[test]
+    fn test_print_rust_struct_narrow_ints() {
+        let json_value = json!([
+            { "age": 30, "views": 2147483647, "balance": -3, "ids": [1, 2] },
+            { "age": 25, "views": 2147483648u64, "balance": 0.5, "ids": [-2147483648] }
+        ]);
+        let options = RustOptions {
+            narrow_ints: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output =
+            "struct Data {\nage: i32;\nbalance: f64;\nids: Vec<i32>;\nviews: i64;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_prefer_f32() {
+        let json_value = json!([
+            { "price": 9.99, "ratio": 0.1, "score": 1, "exact": 0.123456789012, "big": 1e40 },
+            { "price": 12.5, "ratio": 0.25, "score": 2.5, "exact": 1.5, "big": 2.0 }
+        ]);
+        let options = RustOptions {
+            prefer_f32: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output =
+            "struct Data {\nbig: f64;\nexact: f64;\nprice: f32;\nratio: f32;\nscore: f32;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_with_examples() {
+        let json_value = json!([
+            { "name": null, "age": 30, "bio": "She said \"hi\".\nThen she left to write a very long biography." },
+            { "name": "Alice" }
+        ]);
+        let options = RustOptions {
+            examples: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output = "struct Data {\n/// e.g. 30\nage: Option<i64>;\n/// e.g. \"She said \\\"hi\\\".\\nThen she left to write a \"...\nbio: Option<String>;\n/// e.g. \"Alice\"\nname: Option<String>;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_infer_enums() {
+        let json_value = json!([
+            { "status": "active", "role": "admin", "name": "Alice" },
+            { "status": "in-progress", "role": "user", "name": "Bob" },
+            { "status": "active", "role": "guest", "name": "Carol" }
+        ]);
+        let options = RustOptions {
+            infer_enums: Some(2),
+            ..RustOptions::default()
+        };
+
+        let expected_output = "struct Data {\nname: String;\nrole: String;\nstatus: Status;\n}\n\nenum Status {\n#[serde(rename = \"active\")]\nActive;\n#[serde(rename = \"in-progress\")]\nInProgress;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_infer_enums_reused_struct() {
+        let json_value = json!([{
+            "home": { "id": 1, "kind": "house" },
+            "work": { "id": 2, "kind": "flat" },
+        }, {
+            "home": { "id": 3, "kind": "flat" },
+            "work": { "id": 4, "kind": "house" },
+        }]);
+        let options = RustOptions {
+            infer_enums: Some(2),
+            ..RustOptions::default()
+        };
+
+        let expected_output = "struct Data {\nhome: Home;\nwork: Home;\n}\n\nstruct Home {\nid: i64;\nkind: Kind;\n}\n\nenum Kind {\n#[serde(rename = \"flat\")]\nFlat;\n#[serde(rename = \"house\")]\nHouse;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_builder() {
+        let json_value = json!([{ "name": "Alice", "age": 30 }, { "name": "Bob" }]);
+        let options = RustOptions {
+            builder: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output = "struct Data {
+    age: Option<i64>,
+    name: String,
+}
+
+struct DataBuilder {
+    age: Option<i64>,
+    name: String,
+}
+
+impl DataBuilder {
+    fn new(name: String) -> Self {
+        DataBuilder {
+            age: None,
+            name,
+        }
+    }
+
+    fn age(mut self, age: i64) -> Self {
+        self.age = Some(age);
+        self
+    }
+
+    fn build(self) -> Data {
+        Data {
+            age: self.age,
+            name: self.name,
+        }
+    }
+}";
+
+        let actual_output = print_rust_struct(json_value.as_array().unwrap(), &with_rust(&options));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_accessors() {
+        let json_value = json!([
+            { "name": "Alice", "age": 30, "address": { "city": "Paris" } },
+            { "name": "Bob", "address": { "city": "Rome" } }
+        ]);
+        let options = RustOptions {
+            accessors: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output = "struct Data {
+    address: Address,
+    age: Option<i64>,
+    name: String,
+}
+
+impl Data {
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    pub fn age(&self) -> Option<i64> {
+        self.age
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+}
+
+struct Address {
+    city: String,
+}
+
+impl Address {
+    pub fn city(&self) -> &String {
+        &self.city
+    }
+}";
+
+        let actual_output = print_rust_struct(json_value.as_array().unwrap(), &with_rust(&options));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_with_extra() {
+        let json_value =
+            json!([{ "name": "Alice", "extra": true, "address": { "city": "Paris" } }]);
+        let options = RustOptions {
+            extra: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output = "struct Data {\naddress: Address;\nextra: bool;\nname: String;\n#[serde(flatten)]\nextra_: HashMap<String, Value>;\n}\n\nstruct Address {\ncity: String;\n#[serde(flatten)]\nextra: HashMap<String, Value>;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_rust_struct_skip_none() {
+        let json_value = json!([{ "name": "Alice", "age": 30 }, { "name": "Bob" }]);
+        let options = RustOptions {
+            skip_none: true,
+            ..RustOptions::default()
+        };
+
+        let expected_output = "struct Data {\n#[serde(default, skip_serializing_if = \"Option::is_none\")]\nage: Option<i64>;\nname: String;\n}";
+
+        let actual_output = format_output(&print_rust_struct(
+            json_value.as_array().unwrap(),
+            &with_rust(&options),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+}
@@ -0,0 +1,282 @@
+//! A language-independent description of the inferred shape of the input.
+//!
+//! The JSON Schema, GraphQL and OpenAPI emitters are built on it. The others read the
+//! sample values directly, for details the tree leaves out such as string formats.
+
+use crate::fields::{array_elements, merge_fields};
+use crate::Options;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// The type inferred for a set of observed JSON values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InferredType {
+    /// Nothing is known about the type, e.g. the elements of arrays that were always
+    /// empty.
+    Unknown,
+    Scalar(ScalarType),
+    /// An object with its fields, in the order the emitters list them.
+    Object(Vec<InferredField>),
+    Array(Box<InferredType>),
+    /// A value that is `null` in some of the observations.
+    Optional(Box<InferredType>),
+    /// Values of several incompatible types, one member per kind of value.
+    Union(Vec<InferredType>),
+}
+
+impl InferredType {
+    /// Serializes the type tree to JSON for other tools to consume. Every type is an
+    /// object with a `kind`, one of `unknown`, `bool`, `integer`, `float`, `string`,
+    /// `object`, `array`, `optional` or `union`, plus:
+    ///
+    /// - `object`: `fields`, a list of `{"name", "required", "type"}` in output order.
+    /// - `array`: `items`, the type of the elements.
+    /// - `optional`: `type`, the type of the non-null values.
+    /// - `union`: `members`, the types of the incompatible values.
+    pub fn to_json(&self) -> Value {
+        match self {
+            InferredType::Unknown => json!({ "kind": "unknown" }),
+            InferredType::Scalar(scalar) => json!({ "kind": scalar_kind(*scalar) }),
+            InferredType::Object(fields) => json!({
+                "kind": "object",
+                "fields": fields
+                    .iter()
+                    .map(|field| json!({
+                        "name": field.name,
+                        "required": field.required,
+                        "type": field.ty.to_json(),
+                    }))
+                    .collect::<Vec<Value>>(),
+            }),
+            InferredType::Array(items) => json!({ "kind": "array", "items": items.to_json() }),
+            InferredType::Optional(inner) => json!({ "kind": "optional", "type": inner.to_json() }),
+            InferredType::Union(members) => json!({
+                "kind": "union",
+                "members": members.iter().map(InferredType::to_json).collect::<Vec<Value>>(),
+            }),
+        }
+    }
+}
+
+/// Names a scalar type the way `to_json` and `Display` do.
+fn scalar_kind(scalar: ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::Bool => "bool",
+        ScalarType::Integer => "integer",
+        ScalarType::Float => "float",
+        ScalarType::String => "string",
+    }
+}
+
+impl fmt::Display for InferredType {
+    /// Describes the type in words, e.g. `array of integer` or `string | null`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InferredType::Unknown => write!(f, "unknown"),
+            InferredType::Scalar(scalar) => write!(f, "{}", scalar_kind(*scalar)),
+            InferredType::Object(_) => write!(f, "object"),
+            InferredType::Array(items) => write!(f, "array of {}", items),
+            InferredType::Optional(inner) => write!(f, "{} | null", inner),
+            InferredType::Union(members) => {
+                let members: Vec<String> = members.iter().map(|m| m.to_string()).collect();
+                write!(f, "{}", members.join(" | "))
+            }
+        }
+    }
+}
+
+/// The JSON scalar types. Integers and floats seen together merge into `Float`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ScalarType {
+    Bool,
+    Integer,
+    Float,
+    String,
+}
+
+/// A field of an inferred object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InferredField {
+    /// The JSON key.
+    pub name: String,
+    pub ty: InferredType,
+    /// Set when the key is present in every object, even if it is sometimes `null`.
+    pub required: bool,
+}
+
+/// Infers the type of `values`, usually a list of sample objects, with fields sorted
+/// by key.
+pub fn infer_schema(values: &[Value]) -> InferredType {
+    infer_schema_with(values, &Options::default())
+}
+
+/// Infers the type of `values` like [`infer_schema`], applying the options that affect
+/// inference: `preserve_order`, `all_optional` and `all_required`.
+pub fn infer_schema_with(values: &[Value], options: &Options) -> InferredType {
+    let values: Vec<&Value> = values.iter().collect();
+    infer_type(&values, options)
+}
+
+/// Infers the type of `values`, listing fields as `options` asks.
+pub(crate) fn infer_type(values: &[&Value], options: &Options) -> InferredType {
+    let present: Vec<&Value> = values.iter().filter(|v| !v.is_null()).copied().collect();
+    let ty = if present.is_empty() {
+        InferredType::Unknown
+    } else {
+        let mut members = Vec::new();
+        let scalars: BTreeSet<ScalarType> = present.iter().filter_map(|v| scalar_type(v)).collect();
+        if scalars.contains(&ScalarType::Bool) {
+            members.push(InferredType::Scalar(ScalarType::Bool));
+        }
+        if scalars.contains(&ScalarType::Float) {
+            members.push(InferredType::Scalar(ScalarType::Float));
+        } else if scalars.contains(&ScalarType::Integer) {
+            members.push(InferredType::Scalar(ScalarType::Integer));
+        }
+        if scalars.contains(&ScalarType::String) {
+            members.push(InferredType::Scalar(ScalarType::String));
+        }
+        let arrays: Vec<&Value> = present.iter().filter(|v| v.is_array()).copied().collect();
+        if !arrays.is_empty() {
+            let elements = array_elements(&arrays);
+            members.push(InferredType::Array(Box::new(infer_type(
+                &elements, options,
+            ))));
+        }
+        let objects: Vec<&Value> = present.iter().filter(|v| v.is_object()).copied().collect();
+        if !objects.is_empty() {
+            members.push(infer_object(&objects, options));
+        }
+
+        if members.len() == 1 {
+            members.remove(0)
+        } else {
+            InferredType::Union(members)
+        }
+    };
+
+    if present.len() < values.len() {
+        InferredType::Optional(Box::new(ty))
+    } else {
+        ty
+    }
+}
+
+fn infer_object(objects: &[&Value], options: &Options) -> InferredType {
+    let fields = merge_fields(objects, options)
+        .into_iter()
+        .map(|(name, field)| InferredField {
+            ty: match infer_type(&field.values, options) {
+                InferredType::Optional(inner) if options.all_required => *inner,
+                ty => ty,
+            },
+            required: !field.is_missing,
+            name,
+        })
+        .collect();
+    InferredType::Object(fields)
+}
+
+fn scalar_type(value: &Value) -> Option<ScalarType> {
+    match value {
+        Value::Bool(_) => Some(ScalarType::Bool),
+        Value::Number(n) if n.is_f64() => Some(ScalarType::Float),
+        Value::Number(_) => Some(ScalarType::Integer),
+        Value::String(_) => Some(ScalarType::String),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_infer_schema() {
+        let json_value = json!([
+            { "id": 1, "score": 2, "tags": ["a"], "address": { "city": "Paris" }, "code": "x" },
+            { "id": 2, "score": 2.5, "tags": [], "nickname": null, "code": 7 }
+        ]);
+
+        let field = |name: &str, ty, required| InferredField {
+            name: name.to_string(),
+            ty,
+            required,
+        };
+        let scalar = InferredType::Scalar;
+        assert_eq!(
+            infer_schema(json_value.as_array().unwrap()),
+            InferredType::Object(vec![
+                field(
+                    "address",
+                    InferredType::Object(vec![field("city", scalar(ScalarType::String), true)]),
+                    false
+                ),
+                field(
+                    "code",
+                    InferredType::Union(vec![
+                        scalar(ScalarType::Integer),
+                        scalar(ScalarType::String)
+                    ]),
+                    true
+                ),
+                field("id", scalar(ScalarType::Integer), true),
+                field(
+                    "nickname",
+                    InferredType::Optional(Box::new(InferredType::Unknown)),
+                    false
+                ),
+                field("score", scalar(ScalarType::Float), true),
+                field(
+                    "tags",
+                    InferredType::Array(Box::new(scalar(ScalarType::String))),
+                    true
+                ),
+            ])
+        );
+
+        let tags = InferredType::Optional(Box::new(InferredType::Array(Box::new(
+            InferredType::Union(vec![
+                scalar(ScalarType::Integer),
+                scalar(ScalarType::String),
+            ]),
+        ))));
+        assert_eq!(tags.to_string(), "array of integer | string | null");
+        assert_eq!(
+            tags.to_json(),
+            json!({ "kind": "optional", "type": { "kind": "array", "items": {
+                "kind": "union", "members": [{ "kind": "integer" }, { "kind": "string" }]
+            } } })
+        );
+    }
+
+    #[test]
+    fn test_infer_schema_with() {
+        let json_value = json!([{ "b": 1, "a": null }, { "b": 2 }]);
+        let values = json_value.as_array().unwrap();
+
+        let options = Options {
+            preserve_order: true,
+            all_optional: true,
+            ..Options::default()
+        };
+        let InferredType::Object(fields) = infer_schema_with(values, &options) else {
+            panic!("expected an object");
+        };
+        let names: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+        assert_eq!(names, ["b", "a"]);
+        assert!(fields.iter().all(|field| !field.required));
+
+        let options = Options {
+            all_required: true,
+            ..Options::default()
+        };
+        let InferredType::Object(fields) = infer_schema_with(values, &options) else {
+            panic!("expected an object");
+        };
+        assert!(fields.iter().all(|field| field.required));
+        assert_eq!(fields[0].ty, InferredType::Unknown);
+    }
+}
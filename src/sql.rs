@@ -0,0 +1,110 @@
+//! SQL `CREATE TABLE` generation.
+
+use crate::fields::merge_fields;
+use crate::naming::{dedupe_field_names, snake_case};
+use crate::Options;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Prints a `CREATE TABLE` statement with a column for every key. Required fields are
+/// `NOT NULL`, and nested objects and arrays are stored as `JSONB`. Table and column
+/// names are quoted, so keys such as `user` or `order` don't clash with keywords.
+pub fn print_sql_table(values: &[Value], options: &Options) -> String {
+    let values: Vec<&Value> = values.iter().collect();
+    let indent = options.indent.as_deref().unwrap_or("    ");
+    let fields = merge_fields(&values, options);
+    let keys: Vec<&str> = fields.iter().map(|(key, _)| key.as_str()).collect();
+    let mut names: Vec<String> = keys.iter().map(|key| snake_case(key)).collect();
+    dedupe_field_names(&keys, &mut names);
+    let columns: Vec<String> = fields
+        .iter()
+        .zip(&names)
+        .map(|((_, field), name)| {
+            format!(
+                "{}{} {}{}",
+                indent,
+                quote_identifier(name),
+                sql_field_type(&field.values),
+                if field.is_optional { "" } else { " NOT NULL" }
+            )
+        })
+        .collect();
+
+    format!(
+        "CREATE TABLE {} (\n{}\n);",
+        quote_identifier(&snake_case(&options.name)),
+        columns.join(",\n")
+    )
+}
+
+/// Quotes an identifier, doubling any `"` inside it.
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Merges the column types of `values`, treating a mix of `BIGINT` and
+/// `DOUBLE PRECISION` as `DOUBLE PRECISION` and anything else that conflicts as `JSONB`.
+fn sql_field_type(values: &[&Value]) -> &'static str {
+    let types: BTreeSet<&str> = values
+        .iter()
+        .filter(|value| !value.is_null())
+        .map(|value| sql_value_type(value))
+        .collect();
+    match types.into_iter().collect::<Vec<&str>>().as_slice() {
+        [ty] => ty,
+        ["BIGINT", "DOUBLE PRECISION"] => "DOUBLE PRECISION",
+        _ => "JSONB",
+    }
+}
+
+/// Maps a single JSON value to its SQL column type.
+pub fn sql_value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Bool(_) => "BOOLEAN",
+        Value::Number(n) if n.is_f64() => "DOUBLE PRECISION",
+        Value::Number(_) => "BIGINT",
+        Value::String(_) => "TEXT",
+        Value::Null | Value::Array(_) | Value::Object(_) => "JSONB",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_print_sql_table() {
+        let json_value = json!([
+            { "firstName": "Alice", "age": 30, "score": 1, "tags": ["a"], "active": true },
+            { "firstName": "Bob", "age": 25, "score": 2.5, "tags": [], "address": { "city": "Oslo" } }
+        ]);
+        let options = Options {
+            name: "UserProfile".to_string(),
+            ..Options::default()
+        };
+
+        let expected_output = "CREATE TABLE \"user_profile\" (\n    \"active\" BOOLEAN,\n    \"address\" JSONB,\n    \"age\" BIGINT NOT NULL,\n    \"first_name\" TEXT NOT NULL,\n    \"score\" DOUBLE PRECISION NOT NULL,\n    \"tags\" JSONB NOT NULL\n);";
+
+        assert_eq!(
+            print_sql_table(json_value.as_array().unwrap(), &options),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_sql_table_reserved_names() {
+        let json_value = json!([{ "user": "alice", "order": 1, "group": "a", "Group": "b" }]);
+        let options = Options {
+            name: "Select".to_string(),
+            ..Options::default()
+        };
+
+        let expected_output = "CREATE TABLE \"select\" (\n    \"group_2\" TEXT NOT NULL,\n    \"group\" TEXT NOT NULL,\n    \"order\" BIGINT NOT NULL,\n    \"user\" TEXT NOT NULL\n);";
+
+        assert_eq!(
+            print_sql_table(json_value.as_array().unwrap(), &options),
+            expected_output
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Summary statistics about the fields observed in the input.
+
+use crate::fields::merge_fields;
+use crate::jsonschema::json_schema_type;
+use crate::Options;
+use serde_json::Value;
+
+/// One row of the stats table.
+struct FieldStats {
+    path: String,
+    types: Vec<&'static str>,
+    present: usize,
+    nulls: usize,
+    optional: bool,
+}
+
+/// Prints a summary of the objects in `values`: how many were scanned, and a table
+/// with the observed types and the present and null counts of every field. Fields of
+/// nested objects are listed with a dotted path.
+pub fn print_stats(values: &[Value], options: &Options) -> String {
+    let values: Vec<&Value> = values.iter().collect();
+    let mut rows = Vec::new();
+    collect_stats("", &values, options, &mut rows);
+
+    let objects = values.iter().filter(|value| value.is_object()).count();
+    let optional = rows.iter().filter(|row| row.optional).count();
+    let mut output = format!(
+        "{} objects scanned, {} distinct fields ({} required, {} optional)\n",
+        objects,
+        rows.len(),
+        rows.len() - optional,
+        optional
+    );
+
+    let table: Vec<[String; 4]> = rows
+        .iter()
+        .map(|row| {
+            [
+                row.path.clone(),
+                row.types.join("|"),
+                row.present.to_string(),
+                row.nulls.to_string(),
+            ]
+        })
+        .collect();
+    let header = ["FIELD", "TYPES", "PRESENT", "NULL"].map(String::from);
+    let mut widths = header.clone().map(|cell| cell.len());
+    for row in &table {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    for row in std::iter::once(&header).chain(&table) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect();
+        output.push_str(line.join("  ").trim_end());
+        output.push('\n');
+    }
+    output.pop();
+    output
+}
+
+fn collect_stats(prefix: &str, values: &[&Value], options: &Options, rows: &mut Vec<FieldStats>) {
+    for (key, field) in merge_fields(values, options) {
+        let path = format!("{}{}", prefix, key);
+        let mut types = Vec::new();
+        for value in field.values.iter().filter(|value| !value.is_null()) {
+            let ty = json_schema_type(value);
+            if !types.contains(&ty) {
+                types.push(ty);
+            }
+        }
+        let nulls = field.values.iter().filter(|value| value.is_null()).count();
+        rows.push(FieldStats {
+            path: path.clone(),
+            types,
+            present: field.values.len(),
+            nulls,
+            optional: field.is_optional,
+        });
+
+        let objects: Vec<&Value> = field
+            .values
+            .iter()
+            .filter(|value| value.is_object())
+            .copied()
+            .collect();
+        if !objects.is_empty() {
+            collect_stats(&format!("{}.", path), &objects, options, rows);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_print_stats() {
+        let json_value = json!([
+            { "name": "Alice", "age": 30, "address": { "city": "Paris" } },
+            { "name": "Bob", "age": 25.5, "address": null },
+            { "name": "Carol" }
+        ]);
+
+        let expected_output = "3 objects scanned, 4 distinct fields (2 required, 2 optional)
+FIELD         TYPES           PRESENT  NULL
+address       object          2        1
+address.city  string          1        0
+age           integer|number  2        0
+name          string          3        0";
+
+        assert_eq!(
+            print_stats(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Parsing JSON input one value at a time for `--stream`, so a multi-gigabyte array
+//! is never held in memory as text and as a parsed tree at once.
+
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+
+/// Most distinct scalars kept for one field. Past it, only numbers of a new kind or
+/// beyond the range seen so far are kept, so an `id` doesn't grow with the input.
+const MAX_DISTINCT_SCALARS: usize = 1000;
+
+/// A running merge of the values parsed so far. Each value is folded in and dropped,
+/// keeping only its distinct scalars and, for objects, a merge per set of keys, so the
+/// memory grows with the variety of the input rather than with the number of objects.
+#[derive(Default)]
+pub(crate) struct Accumulator {
+    count: usize,
+    /// Distinct scalars, in the order first seen, and whether each one was repeated.
+    scalars: Vec<(Value, bool)>,
+    scalar_positions: HashMap<String, usize>,
+    /// The smallest and largest number of each kind seen past the distinct scalars.
+    number_ranges: Vec<(&'static str, Value, Value)>,
+    shapes: Vec<Shape>,
+    shape_positions: HashMap<Vec<String>, usize>,
+    /// The elements of every non-empty array, merged together.
+    elements: Option<Box<Accumulator>>,
+    has_empty_array: bool,
+}
+
+/// The objects with one set of keys, in the order the first of them had the keys.
+struct Shape {
+    fields: Vec<(String, Accumulator)>,
+}
+
+impl Accumulator {
+    /// The number of values folded in.
+    pub(crate) fn count(&self) -> usize {
+        self.count
+    }
+
+    pub(crate) fn fold(&mut self, value: Value) {
+        self.count += 1;
+        match value {
+            Value::Object(map) => {
+                let mut keys: Vec<String> = map.keys().cloned().collect();
+                keys.sort_unstable();
+                let shapes = &mut self.shapes;
+                let position = *self.shape_positions.entry(keys).or_insert_with(|| {
+                    let fields = map
+                        .keys()
+                        .map(|key| (key.clone(), Accumulator::default()))
+                        .collect();
+                    shapes.push(Shape { fields });
+                    shapes.len() - 1
+                });
+                let fields = &mut self.shapes[position].fields;
+                for (key, value) in map {
+                    let (_, field) = fields
+                        .iter_mut()
+                        .find(|(existing, _)| *existing == key)
+                        .expect("the shape has every key of the object");
+                    field.fold(value);
+                }
+            }
+            Value::Array(items) if items.is_empty() => self.has_empty_array = true,
+            Value::Array(items) => {
+                let elements = self.elements.get_or_insert_with(Default::default);
+                for item in items {
+                    elements.fold(item);
+                }
+            }
+            scalar => match self.scalar_positions.get(&scalar.to_string()) {
+                Some(&position) => self.scalars[position].1 = true,
+                None if self.scalars.len() < MAX_DISTINCT_SCALARS => {
+                    self.scalar_positions
+                        .insert(scalar.to_string(), self.scalars.len());
+                    self.scalars.push((scalar, false));
+                }
+                None => self.fold_number(scalar),
+            },
+        }
+    }
+
+    /// Widens the range of the number's kind, ignoring scalars of any other type.
+    fn fold_number(&mut self, scalar: Value) {
+        let (kind, n) = match &scalar {
+            Value::Number(number) if number.is_u64() => ("unsigned", number.as_f64()),
+            Value::Number(number) if number.is_i64() => ("signed", number.as_f64()),
+            Value::Number(number) => ("float", number.as_f64()),
+            _ => return,
+        };
+        let n = n.unwrap_or_default();
+        match self.number_ranges.iter_mut().find(|(k, _, _)| *k == kind) {
+            Some((_, min, _)) if n < min.as_f64().unwrap_or_default() => *min = scalar,
+            Some((_, _, max)) if n > max.as_f64().unwrap_or_default() => *max = scalar,
+            Some(_) => {}
+            None => self.number_ranges.push((kind, scalar.clone(), scalar)),
+        }
+    }
+
+    /// Builds a few values that merge into the same types as everything folded in:
+    /// every distinct scalar, twice if it was repeated, and for every set of keys
+    /// enough objects to hold each distinct value of their fields.
+    pub(crate) fn into_values(self) -> Vec<Value> {
+        let mut values = Vec::new();
+        for (scalar, repeated) in self.scalars {
+            if repeated {
+                values.push(scalar.clone());
+            }
+            values.push(scalar);
+        }
+        for (_, min, max) in self.number_ranges {
+            if min != max {
+                values.push(min);
+            }
+            values.push(max);
+        }
+        for shape in self.shapes {
+            let fields: Vec<(String, Vec<Value>)> = shape
+                .fields
+                .into_iter()
+                .map(|(key, field)| (key, field.into_values()))
+                .collect();
+            let rows = fields.iter().map(|(_, values)| values.len()).max();
+            for row in 0..rows.unwrap_or(0).max(1) {
+                let object: Map<String, Value> = fields
+                    .iter()
+                    .map(|(key, values)| (key.clone(), values[row % values.len()].clone()))
+                    .collect();
+                values.push(Value::Object(object));
+            }
+        }
+        if let Some(elements) = self.elements {
+            values.push(Value::Array(elements.into_values()));
+        }
+        if self.has_empty_array {
+            values.push(Value::Array(Vec::new()));
+        }
+        values
+    }
+}
+
+/// Folds the elements of a top-level array, or a single top-level object, into an
+/// [`Accumulator`] as they are parsed. Elements past `limit` are skipped without being
+/// built.
+struct Collector<'a> {
+    accumulator: &'a mut Accumulator,
+    limit: Option<usize>,
+}
+
+impl Collector<'_> {
+    fn is_full(&self) -> bool {
+        self.limit
+            .is_some_and(|limit| self.accumulator.count() >= limit)
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Collector<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Collector<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an object or an array of objects")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while !self.is_full() {
+            match seq.next_element::<Value>()? {
+                Some(value) => self.accumulator.fold(value),
+                None => return Ok(()),
+            }
+        }
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<(), A::Error> {
+        let value = Value::deserialize(MapAccessDeserializer::new(map))?;
+        if !self.is_full() {
+            self.accumulator.fold(value);
+        }
+        Ok(())
+    }
+}
+
+/// Parses a JSON document holding an object or an array of objects from `reader`,
+/// folding at most `limit` of them into `accumulator`.
+pub(crate) fn stream_json(
+    reader: impl Read,
+    limit: Option<usize>,
+    accumulator: &mut Accumulator,
+) -> serde_json::Result<()> {
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    Collector { accumulator, limit }.deserialize(&mut deserializer)?;
+    deserializer.end()
+}
+
+/// Parses newline-delimited JSON from `reader`, folding at most `limit` values into
+/// `accumulator` and reading no further once it is reached.
+pub(crate) fn stream_ndjson(
+    reader: impl Read,
+    limit: Option<usize>,
+    accumulator: &mut Accumulator,
+) -> serde_json::Result<()> {
+    let stream = serde_json::Deserializer::from_reader(reader).into_iter::<Value>();
+    for value in stream.take(limit.unwrap_or(usize::MAX)) {
+        accumulator.fold(value?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use json_to_struct::{generate, Language, Options};
+    use serde_json::json;
+
+    #[test]
+    fn test_stream_json() {
+        let mut accumulator = Accumulator::default();
+        stream_json(
+            r#"[{"a": 1}, {"a": 2}, {"a": 3}]"#.as_bytes(),
+            Some(2),
+            &mut accumulator,
+        )
+        .unwrap();
+        assert_eq!(accumulator.count(), 2);
+
+        stream_json(r#"{"b": true}"#.as_bytes(), None, &mut accumulator).unwrap();
+        assert_eq!(accumulator.count(), 3);
+        assert!(stream_json("42".as_bytes(), None, &mut accumulator).is_err());
+        assert!(stream_json("[{}] trailing".as_bytes(), None, &mut accumulator).is_err());
+
+        let mut accumulator = Accumulator::default();
+        stream_ndjson(
+            "{\"a\": 1}\n\n{\"a\": 2}\n".as_bytes(),
+            None,
+            &mut accumulator,
+        )
+        .unwrap();
+        assert_eq!(
+            accumulator.into_values(),
+            [json!({"a": 1}), json!({"a": 2})]
+        );
+    }
+
+    #[test]
+    fn test_accumulator() {
+        let mut values: Vec<Value> = (0..1000)
+            .map(|i| {
+                json!({
+                    "id": i % 3,
+                    "status": if i % 2 == 0 { "open" } else { "closed" },
+                    "address": { "city": "Paris", "zip": if i == 7 { json!(null) } else { json!("75001") } },
+                    "tags": if i % 5 == 0 { json!([]) } else { json!(["a", 1.5]) }
+                })
+            })
+            .collect();
+        values.push(json!({ "id": 4, "note": "late" }));
+
+        let mut accumulator = Accumulator::default();
+        for value in values.clone() {
+            accumulator.fold(value);
+        }
+        let merged = accumulator.into_values();
+        assert!(merged.len() < 10);
+
+        let options = Options {
+            rust: json_to_struct::RustOptions {
+                infer_enums: Some(5),
+                ..Default::default()
+            },
+            ..Options::default()
+        };
+        for language in [Language::Rust, Language::TypeScript] {
+            assert_eq!(
+                generate(&merged, language, &options),
+                generate(&values, language, &options)
+            );
+        }
+
+        // Past the distinct values kept, a number of a new kind still widens the type.
+        let mut accumulator = Accumulator::default();
+        for i in 0..5000 {
+            accumulator.fold(json!({ "id": i, "score": i }));
+        }
+        accumulator.fold(json!({ "id": 5000, "score": 0.5 }));
+        let merged = accumulator.into_values();
+        assert!(merged.len() <= MAX_DISTINCT_SCALARS + 3);
+        assert_eq!(
+            generate(&merged, Language::Rust, &Options::default()),
+            "struct Data {\n    id: i64,\n    score: f64,\n}"
+        );
+    }
+}
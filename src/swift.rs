@@ -0,0 +1,143 @@
+//! Swift `Codable` struct generation.
+
+use crate::fields::{merge_fields, value_field_type};
+use crate::naming::{camel_case, dedupe_field_names};
+use crate::Options;
+use serde_json::Value;
+
+/// Keywords that must be escaped with backticks when used as property names.
+const SWIFT_KEYWORDS: [&str; 30] = [
+    "associatedtype",
+    "case",
+    "class",
+    "default",
+    "defer",
+    "deinit",
+    "do",
+    "else",
+    "enum",
+    "extension",
+    "false",
+    "for",
+    "func",
+    "if",
+    "import",
+    "in",
+    "init",
+    "is",
+    "let",
+    "nil",
+    "protocol",
+    "repeat",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "switch",
+    "true",
+    "var",
+    "while",
+];
+
+/// Prints a `Codable` struct with a `let` property for every key. Optional properties
+/// are optionals, and a `CodingKeys` enum maps the properties back to their JSON keys
+/// whenever one of them was renamed.
+pub fn print_swift_struct(values: &[Value], options: &Options) -> String {
+    let values: Vec<&Value> = values.iter().collect();
+    let indent = options.indent.as_deref().unwrap_or("    ");
+    let mut properties = String::new();
+    let mut cases = String::new();
+    let mut renamed = false;
+
+    let fields = merge_fields(&values, options);
+    let keys: Vec<&str> = fields.iter().map(|(key, _)| key.as_str()).collect();
+    let mut names: Vec<String> = keys.iter().map(|key| camel_case(key)).collect();
+    dedupe_field_names(&keys, &mut names);
+
+    for ((key, field), name) in fields.iter().zip(names) {
+        let ty = swift_field_type(&field.values);
+        let name = if SWIFT_KEYWORDS.contains(&name.as_str()) {
+            format!("`{}`", name)
+        } else {
+            name
+        };
+        let optional = if field.is_optional { "?" } else { "" };
+        properties.push_str(&format!("{}let {}: {}{}\n", indent, name, ty, optional));
+        if name.trim_matches('`') == key {
+            cases.push_str(&format!("{}{}case {}\n", indent, indent, name));
+        } else {
+            renamed = true;
+            cases.push_str(&format!(
+                "{}{}case {} = {}\n",
+                indent,
+                indent,
+                name,
+                Value::String(key.clone())
+            ));
+        }
+    }
+
+    let mut output = format!(
+        "struct {}: Codable {{\n{}",
+        options.type_name(&options.name),
+        properties
+    );
+    if renamed {
+        output.push_str(&format!(
+            "\n{}enum CodingKeys: String, CodingKey {{\n{}{}}}\n",
+            indent, cases, indent
+        ));
+    }
+    output.push('}');
+    output
+}
+
+fn swift_field_type(values: &[&Value]) -> String {
+    value_field_type(values, swift_value_type, "Any", |ty| format!("[{}]", ty))
+}
+
+/// Maps a single JSON value to its Swift type.
+pub fn swift_value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "Any",
+        Value::Bool(_) => "Bool",
+        Value::Number(n) if n.is_f64() => "Double",
+        Value::Number(_) => "Int",
+        Value::String(_) => "String",
+        Value::Array(_) => "[Any]",
+        Value::Object(_) => "[String: Any]",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_print_swift_struct() {
+        let json_value = json!([
+            { "first_name": "Alice", "age": 30, "scores": [1.5, 2], "default": true },
+            { "first_name": "Bob", "age": 25, "scores": [], "address": {} }
+        ]);
+
+        let expected_output = "struct Data: Codable {\n    let address: [String: Any]?\n    let age: Int\n    let `default`: Bool?\n    let firstName: String\n    let scores: [Double]\n\n    enum CodingKeys: String, CodingKey {\n        case address\n        case age\n        case `default`\n        case firstName = \"first_name\"\n        case scores\n    }\n}";
+
+        assert_eq!(
+            print_swift_struct(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_print_swift_struct_colliding_keys() {
+        let json_value = json!([{ "userId": 1, "user_id": 2 }]);
+
+        let expected_output = "struct Data: Codable {\n    let userId: Int\n    let userId2: Int\n\n    enum CodingKeys: String, CodingKey {\n        case userId\n        case userId2 = \"user_id\"\n    }\n}";
+
+        assert_eq!(
+            print_swift_struct(json_value.as_array().unwrap(), &Options::default()),
+            expected_output
+        );
+    }
+}
@@ -0,0 +1,418 @@
+//! TypeScript interface generation.
+
+use crate::fields::{array_elements, merge_fields};
+use crate::naming::pascal_case;
+use crate::Options;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+/// How optional TypeScript properties are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TsNullStyle {
+    /// `name?: string` for every optional property.
+    #[default]
+    Optional,
+    /// `name: string | null` for every optional property.
+    Union,
+    /// `name?: string` when the key is sometimes missing and `| null` when it is
+    /// sometimes `null`, so `name?: string | null` when both happen.
+    Both,
+}
+
+impl FromStr for TsNullStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "optional" => Ok(TsNullStyle::Optional),
+            "union" => Ok(TsNullStyle::Union),
+            "both" => Ok(TsNullStyle::Both),
+            _ => Err(format!(
+                "Invalid null style '{}'. Please use 'optional', 'union' or 'both'.",
+                s
+            )),
+        }
+    }
+}
+
+/// Options that control how TypeScript interfaces are generated.
+#[derive(Debug, Clone, Default)]
+pub struct TypeScriptOptions {
+    pub null_style: TsNullStyle,
+}
+
+/// Accumulates the generated interfaces, in declaration order, while walking the input.
+struct TypeScriptGenerator<'a> {
+    options: &'a Options,
+    indent: &'a str,
+    interfaces: Vec<(String, String)>,
+    /// How many interfaces are being generated around the current one.
+    depth: usize,
+}
+
+impl<'a> TypeScriptGenerator<'a> {
+    fn new(options: &'a Options) -> Self {
+        TypeScriptGenerator {
+            options,
+            indent: options.indent.as_deref().unwrap_or("    "),
+            interfaces: Vec::new(),
+            depth: 0,
+        }
+    }
+
+    /// Picks an interface name that hasn't been generated yet by appending a counter.
+    fn unique_interface_name(&self, name: &str) -> String {
+        let mut candidate = name.to_string();
+        let mut counter = 2;
+        while self
+            .interfaces
+            .iter()
+            .any(|(existing, _)| existing == &candidate)
+        {
+            candidate = format!("{}{}", name, counter);
+            counter += 1;
+        }
+        candidate
+    }
+
+    /// Types a field from all of its non-null values, as a union when they differ,
+    /// e.g. `number | string`.
+    fn field_type(&mut self, key: &str, values: &[&Value]) -> String {
+        let present: Vec<&Value> = values.iter().filter(|v| !v.is_null()).copied().collect();
+        if !present.is_empty() && present.iter().all(|value| value.is_object()) {
+            return self.generate_interface(&self.options.type_name(&pascal_case(key)), &present);
+        }
+        if present.is_empty() {
+            return "null".to_string();
+        }
+        let arrays: Vec<&Value> = present.iter().filter(|v| v.is_array()).copied().collect();
+        let mut types: BTreeSet<String> = present
+            .iter()
+            .filter(|v| !v.is_array())
+            .map(|value| typescript_value_type(value).to_string())
+            .collect();
+        if !arrays.is_empty() {
+            types.insert(self.array_type(key, &arrays));
+        }
+        types.into_iter().collect::<Vec<String>>().join(" | ")
+    }
+
+    /// Infers `T[]` when every element shares a type, falling back to `unknown[]` for
+    /// empty or heterogeneous arrays. Arrays of arrays recurse to any depth.
+    fn array_type(&mut self, key: &str, values: &[&Value]) -> String {
+        let elements = array_elements(values);
+        if !elements.is_empty() && elements.iter().all(|value| value.is_array()) {
+            return format!("{}[]", self.array_type(key, &elements));
+        }
+        if !elements.is_empty() && elements.iter().all(|value| value.is_object()) {
+            return format!(
+                "{}[]",
+                self.generate_interface(
+                    &self
+                        .options
+                        .type_name(&pascal_case(&self.options.element_key(key))),
+                    &elements,
+                )
+            );
+        }
+        let types: BTreeSet<&str> = elements.into_iter().map(typescript_value_type).collect();
+        match types.into_iter().collect::<Vec<&str>>().as_slice() {
+            [ty] if *ty != "null" => format!("{}[]", ty),
+            _ => "unknown[]".to_string(),
+        }
+    }
+
+    /// Generates an interface for the given objects along with any nested interfaces
+    /// it needs. Returns the name given to the interface, or `unknown` for objects
+    /// nested deeper than `--max-depth`.
+    fn generate_interface(&mut self, name: &str, values: &[&Value]) -> String {
+        if self.options.max_depth.is_some_and(|max| self.depth > max) {
+            return "unknown".to_string();
+        }
+        let name = self.unique_interface_name(name);
+        let index = self.interfaces.len();
+        self.interfaces.push((name.clone(), String::new()));
+
+        let mut members = Vec::new();
+        for (key, field) in &merge_fields(values, self.options) {
+            self.depth += 1;
+            let mut ty = self.field_type(key, &field.values);
+            self.depth -= 1;
+            let (question_mark, null_union) = match self.options.typescript.null_style {
+                TsNullStyle::Optional => (field.is_optional, false),
+                TsNullStyle::Union => (false, field.is_optional),
+                TsNullStyle::Both => (field.is_missing, field.is_nullable),
+            };
+            if null_union && ty != "null" {
+                ty.push_str(" | null");
+            }
+            members.push(format!(
+                "{}{}: {};",
+                property_name(key),
+                if question_mark { "?" } else { "" },
+                ty
+            ));
+        }
+        self.interfaces[index].1 = match self.options.compact {
+            Some(_) if members.is_empty() => format!("interface {} {{}}", name),
+            Some(max) if members.len() <= max => {
+                format!("interface {} {{ {} }}", name, members.join(" "))
+            }
+            _ => {
+                let members: String = members
+                    .iter()
+                    .map(|member| format!("{}{}\n", self.indent, member))
+                    .collect();
+                format!("interface {} {{\n{}}}", name, members)
+            }
+        };
+        name
+    }
+}
+
+/// Prints the root interface followed by an interface for every nested object. When
+/// the input holds no objects at all, e.g. `["a", "b"]`, the root is a type alias for
+/// the inferred array type instead.
+pub fn print_typescript_interface(values: &[Value], options: &Options) -> String {
+    let mut generator = TypeScriptGenerator::new(options);
+    let mut output = Vec::new();
+    if !values.is_empty() && !values.iter().any(|value| value.is_object()) {
+        let root = Value::Array(values.to_vec());
+        let ty = generator.array_type(&options.name, &[&root]);
+        output.push(format!(
+            "type {} = {};",
+            options.type_name(&options.name),
+            ty
+        ));
+    } else {
+        let values: Vec<&Value> = values.iter().collect();
+        generator.generate_interface(&options.type_name(&options.name), &values);
+    }
+
+    output.extend(generator.interfaces.into_iter().map(|(_, body)| body));
+    output.join("\n\n")
+}
+
+/// Writes a key as a property name, quoting it when it isn't a valid identifier.
+fn property_name(key: &str) -> String {
+    let mut chars = key.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+/// Maps a single JSON value to its TypeScript type.
+pub fn typescript_value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "unknown[]",
+        Value::Object(_) => "Record<string, unknown>",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_output;
+    use serde_json::json;
+
+    #[test]
+    fn test_print_typescript_interface() {
+        let json_value = json!([
+            {
+                "name": "Alice",
+                "age": 30.0,
+                "is_student": false
+            },
+            {
+                "name": "Bob",
+                "age": 25.0,
+                "is_student": true
+            }
+        ]);
+
+        let expected_output =
+            "interface Data {\nage: number;\nis_student: boolean;\nname: string;\n}";
+
+        let actual_output = format_output(&print_typescript_interface(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_typescript_interface_partial_fields() {
+        let json_value = json!([
+            {
+                "name": "Alice",
+            },
+            {
+                "name": "Bob",
+                "is_student": true,
+            }
+        ]);
+
+        let expected_output = "interface Data {\nis_student?: boolean;\nname: string;\n}";
+
+        let actual_output = format_output(&print_typescript_interface(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_typescript_interface_null_style() {
+        let json_value = json!([
+            { "name": "Alice", "age": null, "email": "a@example.com" },
+            { "name": "Bob", "age": 30 }
+        ]);
+        let with_style = |null_style| Options {
+            typescript: TypeScriptOptions { null_style },
+            ..Options::default()
+        };
+
+        let actual_output = format_output(&print_typescript_interface(
+            json_value.as_array().unwrap(),
+            &with_style(TsNullStyle::Union),
+        ));
+        assert_eq!(
+            actual_output,
+            "interface Data {\nage: number | null;\nemail: string | null;\nname: string;\n}"
+        );
+
+        let actual_output = format_output(&print_typescript_interface(
+            json_value.as_array().unwrap(),
+            &with_style(TsNullStyle::Both),
+        ));
+        assert_eq!(
+            actual_output,
+            "interface Data {\nage: number | null;\nemail?: string;\nname: string;\n}"
+        );
+        assert_eq!("both".parse(), Ok(TsNullStyle::Both));
+    }
+
+    #[test]
+    fn test_print_typescript_interface_array_elements() {
+        let json_value = json!([
+            { "tags": ["a", "b"], "scores": [1, 2.5], "mixed": [1, "two"], "grid": [[[1]], [[2, 3]]], "empty": [] },
+            { "tags": [], "scores": [3], "mixed": [], "grid": [], "empty": [] }
+        ]);
+
+        let expected_output = "interface Data {\nempty: unknown[];\ngrid: number[][][];\nmixed: unknown[];\nscores: number[];\ntags: string[];\n}";
+
+        let actual_output = format_output(&print_typescript_interface(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_typescript_interface_primitive_array() {
+        let json_value = json!([["a"], ["b", "c"]]);
+        let output =
+            print_typescript_interface(json_value.as_array().unwrap(), &Options::default());
+        assert_eq!(output, "type Data = string[][];");
+    }
+
+    #[test]
+    fn test_print_typescript_interface_nested_objects() {
+        let json_value = json!([
+            { "address": { "city": "Paris" }, "orders": [{ "id": 1 }] },
+            { "address": { "city": "Oslo", "zip": "0150" }, "orders": [] }
+        ]);
+
+        let expected_output = "interface Data {\naddress: Address;\norders: Order[];\n}\n\ninterface Address {\ncity: string;\nzip?: string;\n}\n\ninterface Order {\nid: number;\n}";
+
+        let actual_output = format_output(&print_typescript_interface(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+
+        let options = Options {
+            singularize: false,
+            ..Options::default()
+        };
+        let actual_output = print_typescript_interface(json_value.as_array().unwrap(), &options);
+        assert!(actual_output.contains("orders: Orders[];"));
+    }
+
+    #[test]
+    fn test_print_typescript_interface_compact() {
+        let json_value = json!([{
+            "name": "Alice",
+            "age": 30,
+            "address": { "city": "Paris", "zip": "75001", "street": "Rue", "country": "FR" },
+            "meta": {}
+        }]);
+        let options = Options {
+            compact: Some(3),
+            ..Options::default()
+        };
+
+        let expected_output =
+            "interface Data { address: Address; age: number; meta: Meta; name: string; }";
+        let output = print_typescript_interface(json_value.as_array().unwrap(), &options);
+        assert!(output.starts_with("interface Data {\n    address: Address;\n"));
+        assert!(output.ends_with("interface Meta {}"));
+
+        let options = Options {
+            compact: Some(4),
+            ..options
+        };
+        let output = print_typescript_interface(json_value.as_array().unwrap(), &options);
+        assert_eq!(
+            output,
+            format!(
+                "{}\n\ninterface Address {{ city: string; country: string; street: string; zip: string; }}\n\ninterface Meta {{}}",
+                expected_output
+            )
+        );
+    }
+
+    #[test]
+    fn test_print_typescript_interface_all_optional() {
+        let json_value = json!([{ "name": "Alice", "address": { "city": "Paris" } }]);
+        let options = Options {
+            all_optional: true,
+            ..Options::default()
+        };
+
+        let expected_output =
+            "interface Data {\naddress?: Address;\nname?: string;\n}\n\ninterface Address {\ncity?: string;\n}";
+        let actual_output = format_output(&print_typescript_interface(
+            json_value.as_array().unwrap(),
+            &options,
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[test]
+    fn test_print_typescript_interface_mixed_types_and_quoted_keys() {
+        let json_value = json!([
+            { "id": 1, "user-id": "a", "q\"k": [1], "$ref": true },
+            { "id": "two", "user-id": null, "q\"k": "x", "$ref": false }
+        ]);
+
+        let expected_output = "interface Data {\n$ref: boolean;\nid: number | string;\n\"q\\\"k\": number[] | string;\n\"user-id\"?: string;\n}";
+
+        let actual_output = format_output(&print_typescript_interface(
+            json_value.as_array().unwrap(),
+            &Options::default(),
+        ));
+        assert_eq!(actual_output, expected_output);
+    }
+}
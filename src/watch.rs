@@ -0,0 +1,45 @@
+//! Regenerating the output whenever an input file changes.
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// How long to wait for further changes before regenerating, so that editors
+/// writing a file in several steps trigger a single regeneration.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Calls `regenerate` every time one of `paths` is modified, until watching fails.
+///
+/// The parent directories are watched rather than the files themselves, so files
+/// replaced by a rename, as many editors save them, are still picked up.
+pub(crate) fn watch(paths: &[String], mut regenerate: impl FnMut()) -> notify::Result<()> {
+    let files = paths
+        .iter()
+        .map(fs::canonicalize)
+        .collect::<Result<Vec<PathBuf>, _>>()?;
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    for file in &files {
+        let dir = file.parent().unwrap_or_else(|| Path::new("/"));
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+    }
+
+    let is_change = |event: &notify::Result<Event>| match event {
+        Ok(event) => {
+            !matches!(event.kind, EventKind::Access(_))
+                && event.paths.iter().any(|path| files.contains(path))
+        }
+        Err(_) => true,
+    };
+    while let Ok(event) = rx.recv() {
+        if !is_change(&event) {
+            continue;
+        }
+        event?;
+        while rx.recv_timeout(DEBOUNCE).is_ok() {}
+        regenerate();
+    }
+    Ok(())
+}